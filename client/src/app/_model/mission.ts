@@ -59,3 +59,8 @@ export interface MissionChatMessage {
     created_at: string;
     image_url?: string;
 }
+
+export interface MissionChatPage {
+    messages: MissionChatMessage[];
+    next_cursor: number | null;
+}
@@ -2,7 +2,7 @@ import { inject, Injectable, PLATFORM_ID, signal } from '@angular/core';
 import { HttpClient, HttpParams } from '@angular/common/http';
 import { isPlatformBrowser } from '@angular/common';
 import { environment } from '../../environments/environment';
-import { AddMission, CrewMember, EditMission, Mission, MissionChatMessage, MissionFilter, UploadedImage } from '../_model/mission';
+import { AddMission, CrewMember, EditMission, Mission, MissionChatMessage, MissionChatPage, MissionFilter, UploadedImage } from '../_model/mission';
 import { firstValueFrom, Subject } from 'rxjs';
 
 @Injectable({
@@ -38,8 +38,8 @@ export class MissionService {
     await firstValueFrom(this._http.post(`${environment.base_url}/api/mission-chat/${missionId}`, { content }));
   }
 
-  async getChatMessages(missionId: number): Promise<MissionChatMessage[]> {
-    return await firstValueFrom(this._http.get<MissionChatMessage[]>(`${environment.base_url}/api/mission-chat/${missionId}`));
+  async getChatMessages(missionId: number): Promise<MissionChatPage> {
+    return await firstValueFrom(this._http.get<MissionChatPage>(`${environment.base_url}/api/mission-chat/${missionId}`));
   }
 
   triggerRefresh() {
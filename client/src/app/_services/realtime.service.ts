@@ -90,8 +90,10 @@ export class RealtimeService {
             }
             this._missionService.triggerRefresh();
             this.refreshMissions();
+        } else if (event.type === 'Mentioned') {
+            this._snackbar.info(`${event.payload.mentioned_by_name} mentioned you: ${event.payload.snippet}`);
         } else if (event.type === 'MissionChatMessage') {
-            this._missionService.receiveChatMessage(event.payload);
+            this._missionService.receiveChatMessage({ ...event.payload, id: event.payload.message_id });
         }
     }
 
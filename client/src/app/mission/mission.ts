@@ -644,8 +644,8 @@ export class MissionComponent implements OnInit, OnDestroy {
 
   async loadChat(missionId: number) {
     try {
-      const msgs = await this._missionService.getChatMessages(missionId);
-      this.chatMessages.set(msgs);
+      const page = await this._missionService.getChatMessages(missionId);
+      this.chatMessages.set(page.messages);
       this.scrollToBottom();
     } catch (e) {
       console.error('Failed to load chat:', e);
@@ -0,0 +1,61 @@
+use std::fmt;
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+#[derive(Debug)]
+pub enum AppError {
+    NotFound(String),
+    Unauthorized(String),
+    Forbidden(String),
+    Conflict(String),
+    Validation(String),
+    Internal(anyhow::Error),
+}
+
+pub type AppResult<T> = std::result::Result<T, AppError>;
+
+impl AppError {
+    // Stable machine-readable code, the frontend switches on this instead of the message
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::Validation(_) => "VALIDATION_ERROR",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound(message)
+            | AppError::Unauthorized(message)
+            | AppError::Forbidden(message)
+            | AppError::Conflict(message)
+            | AppError::Validation(message) => write!(f, "{}", message),
+            AppError::Internal(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+// Repositories still return anyhow errors; the diesel ones we can classify are
+// mapped here so `?` in a use case keeps working.
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast_ref::<DieselError>() {
+            Some(DieselError::NotFound) => AppError::NotFound("Resource not found".to_string()),
+            Some(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                AppError::Conflict("Resource already exists".to_string())
+            }
+            Some(DieselError::DatabaseError(DatabaseErrorKind::CheckViolation, _)) => {
+                AppError::Validation("Invalid value".to_string())
+            }
+            _ => AppError::Internal(error),
+        }
+    }
+}
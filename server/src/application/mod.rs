@@ -1 +1,2 @@
+pub mod errors;
 pub mod use_cases;
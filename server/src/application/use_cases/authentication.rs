@@ -1,51 +1,95 @@
 use std::sync::Arc;
 
-use anyhow::{Ok, Result};
+use crate::application::errors::{AppError, AppResult};
+use chrono::{Duration, Utc};
 
 use crate::{
-    // config::config_loader::get_jwt_env,
-    domain::repositories::brawlers::BrawlerRepository,
+    config::config_loader::get_jwt_refresh_env,
+    domain::{
+        entities::{refresh_tokens::AddRefreshTokenEntity, sessions::AddSessionEntity},
+        repositories::{
+            brawlers::BrawlerRepository, refresh_tokens::RefreshTokenRepository,
+            sessions::SessionRepository,
+        },
+        value_objects::session_model::SessionModel,
+    },
     infrastructure::{
         self,
-        jwt::{authentication_model::LoginModel, jwt_model::Passport},
+        jwt::{
+            authentication_model::LoginModel,
+            jwt_model::{Passport, RefreshClaims},
+        },
     },
 };
 
-pub struct AuthenticationUseCase<T>
+pub struct AuthenticationUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    brawler_repository: Arc<T>,
+    brawler_repository: Arc<T1>,
+    refresh_token_repository: Arc<T2>,
+    session_repository: Arc<T3>,
 }
 
-impl<T> AuthenticationUseCase<T>
+impl<T1, T2, T3> AuthenticationUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    pub fn new(brawler_repository: Arc<T>) -> Self {
-        Self { brawler_repository }
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        refresh_token_repository: Arc<T2>,
+        session_repository: Arc<T3>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            refresh_token_repository,
+            session_repository,
+        }
     }
 
-    pub async fn login(&self, login_model: LoginModel) -> Result<Passport> {
+    pub async fn login(
+        &self,
+        login_model: LoginModel,
+        user_agent: Option<String>,
+    ) -> AppResult<Passport> {
         // let secret_env = get_jwt_env()?;
         // let token_type = "Bearer".to_string();
         // let expires_in = (Utc::now() + Duration::days(1)).timestamp() as usize;
 
         let username = login_model.username.clone();
 
-        let brawler_entity = self.brawler_repository.find_by_username(&username).await?;
+        let brawler_entity = self
+            .brawler_repository
+            .find_by_username(&username)
+            .await
+            .map_err(|_| AppError::Unauthorized("Invalid username or password".to_string()))?;
         let hsah_password = brawler_entity.password;
         let login_password = login_model.password;
 
         if !infrastructure::argon2::verify(login_password, hsah_password)? {
-            return Err(anyhow::anyhow!("Invalid username or password"));
+            return Err(AppError::Unauthorized(
+                "Invalid username or password".to_string(),
+            ));
         }
 
         let (joined_count, completed_count) =
             self.brawler_repository.get_stats(brawler_entity.id).await?;
 
-        let passport = Passport::new(
+        let (session_id, refresh_token) = start_session(
+            self.session_repository.as_ref(),
+            self.refresh_token_repository.as_ref(),
+            brawler_entity.id,
+            user_agent,
+        )
+        .await?;
+
+        let mut passport = Passport::new(
             brawler_entity.id,
+            session_id,
             brawler_entity.display_name,
             brawler_entity.username,
             brawler_entity.avatar_url,
@@ -54,10 +98,11 @@ where
             joined_count,
             completed_count,
         );
+        passport.refresh_token = Some(refresh_token);
 
         Ok(passport)
     }
-    pub async fn line_login(&self, code: &str) -> Result<Passport> {
+    pub async fn line_login(&self, code: &str, user_agent: Option<String>) -> AppResult<Passport> {
         // Load line config
         let line_env = crate::config::config_loader::get_line_env()?;
 
@@ -122,7 +167,7 @@ where
                             );
                             self.brawler_repository.find_by_username(&username).await?
                         } else {
-                            return Err(reg_err);
+                            return Err(reg_err.into());
                         }
                     }
                 }
@@ -132,8 +177,17 @@ where
         let (joined_count, completed_count) =
             self.brawler_repository.get_stats(brawler_entity.id).await?;
 
-        let passport = Passport::new(
+        let (session_id, refresh_token) = start_session(
+            self.session_repository.as_ref(),
+            self.refresh_token_repository.as_ref(),
             brawler_entity.id,
+            user_agent,
+        )
+        .await?;
+
+        let mut passport = Passport::new(
+            brawler_entity.id,
+            session_id,
             brawler_entity.display_name,
             brawler_entity.username,
             brawler_entity.avatar_url,
@@ -142,17 +196,19 @@ where
             joined_count,
             completed_count,
         );
+        passport.refresh_token = Some(refresh_token);
 
         Ok(passport)
     }
 
-    pub async fn get_me(&self, brawler_id: i32) -> Result<Passport> {
+    pub async fn get_me(&self, brawler_id: i32, session_id: &str) -> AppResult<Passport> {
         let brawler_entity = self.brawler_repository.find_by_id(brawler_id).await?;
         let (joined_count, completed_count) =
             self.brawler_repository.get_stats(brawler_entity.id).await?;
 
         let passport = Passport::new(
             brawler_entity.id,
+            session_id.to_string(),
             brawler_entity.display_name,
             brawler_entity.username,
             brawler_entity.avatar_url,
@@ -164,4 +220,185 @@ where
 
         Ok(passport)
     }
+
+    pub async fn refresh_token(&self, refresh_token: String) -> AppResult<Passport> {
+        let refresh_env = get_jwt_refresh_env()?;
+        let claims = infrastructure::jwt::verify_refresh_token(refresh_env.secret, refresh_token)
+            .map_err(|_| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        let token_entity = self
+            .refresh_token_repository
+            .find_by_jti(&claims.jti)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        if token_entity.brawler_id != claims.sub || token_entity.family_id != claims.family_id {
+            return Err(AppError::Unauthorized("Invalid refresh token".to_string()));
+        }
+
+        // A token that was already rotated or revoked is being replayed:
+        // assume it was stolen and kill every token in the family.
+        if token_entity.used_at.is_some() || token_entity.revoked_at.is_some() {
+            self.refresh_token_repository
+                .revoke_family(&token_entity.family_id)
+                .await?;
+            return Err(AppError::Unauthorized(
+                "Refresh token has already been used".to_string(),
+            ));
+        }
+
+        if !self.refresh_token_repository.mark_used(&claims.jti).await? {
+            self.refresh_token_repository
+                .revoke_family(&token_entity.family_id)
+                .await?;
+            return Err(AppError::Unauthorized(
+                "Refresh token has already been used".to_string(),
+            ));
+        }
+
+        // The refresh token family id is the session jti
+        if self
+            .session_repository
+            .find_active_by_jti(&token_entity.family_id)
+            .await?
+            .is_none()
+        {
+            self.refresh_token_repository
+                .revoke_family(&token_entity.family_id)
+                .await?;
+            return Err(AppError::Unauthorized(
+                "Session has been revoked".to_string(),
+            ));
+        }
+
+        let mut passport = self
+            .get_me(token_entity.brawler_id, &token_entity.family_id)
+            .await?;
+        passport.refresh_token = Some(
+            issue_refresh_token(
+                self.refresh_token_repository.as_ref(),
+                token_entity.brawler_id,
+                token_entity.family_id.clone(),
+            )
+            .await?,
+        );
+
+        let refresh_env = get_jwt_refresh_env()?;
+        self.session_repository
+            .touch(
+                &token_entity.family_id,
+                (Utc::now() + Duration::days(refresh_env.life_time_days)).naive_utc(),
+            )
+            .await?;
+
+        Ok(passport)
+    }
+
+    pub async fn get_sessions(
+        &self,
+        brawler_id: i32,
+        current_session_id: &str,
+    ) -> AppResult<Vec<SessionModel>> {
+        let sessions = self
+            .session_repository
+            .get_active_by_brawler(brawler_id)
+            .await?;
+
+        Ok(sessions
+            .iter()
+            .map(|session| session.to_model(current_session_id))
+            .collect())
+    }
+
+    pub async fn revoke_session(&self, brawler_id: i32, session_id: i32) -> AppResult<()> {
+        let jti = self
+            .session_repository
+            .revoke(brawler_id, session_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Session not found".to_string()))?;
+
+        Ok(self.refresh_token_repository.revoke_family(&jti).await?)
+    }
+
+    pub async fn logout(&self, current_session_id: &str) -> AppResult<()> {
+        self.session_repository
+            .revoke_by_jti(current_session_id)
+            .await?;
+        Ok(self
+            .refresh_token_repository
+            .revoke_family(current_session_id)
+            .await?)
+    }
+
+    pub async fn logout_everywhere(&self, brawler_id: i32) -> AppResult<()> {
+        self.session_repository.revoke_all(brawler_id).await?;
+        Ok(self
+            .refresh_token_repository
+            .revoke_all_for_brawler(brawler_id)
+            .await?)
+    }
+}
+
+// Creates a session and the first refresh token of its family. The session
+// jti doubles as the family id so revoking one revokes the other.
+pub async fn start_session<S, R>(
+    session_repository: &S,
+    refresh_token_repository: &R,
+    brawler_id: i32,
+    user_agent: Option<String>,
+) -> AppResult<(String, String)>
+where
+    S: SessionRepository + Send + Sync,
+    R: RefreshTokenRepository + Send + Sync,
+{
+    let refresh_env = get_jwt_refresh_env()?;
+    let session_id = uuid::Uuid::new_v4().to_string();
+
+    session_repository
+        .add(AddSessionEntity {
+            jti: session_id.clone(),
+            brawler_id,
+            user_agent,
+            expires_at: (Utc::now() + Duration::days(refresh_env.life_time_days)).naive_utc(),
+        })
+        .await?;
+
+    let refresh_token =
+        issue_refresh_token(refresh_token_repository, brawler_id, session_id.clone()).await?;
+
+    Ok((session_id, refresh_token))
+}
+
+async fn issue_refresh_token<R>(
+    refresh_token_repository: &R,
+    brawler_id: i32,
+    family_id: String,
+) -> AppResult<String>
+where
+    R: RefreshTokenRepository + Send + Sync,
+{
+    let refresh_env = get_jwt_refresh_env()?;
+    let now = Utc::now();
+    let expires_at = now + Duration::days(refresh_env.life_time_days);
+
+    let claims = RefreshClaims {
+        sub: brawler_id,
+        exp: expires_at.timestamp() as usize,
+        iat: now.timestamp() as usize,
+        jti: uuid::Uuid::new_v4().to_string(),
+        family_id,
+    };
+
+    let token = infrastructure::jwt::generate_refresh_token(refresh_env.secret, &claims)?;
+
+    refresh_token_repository
+        .add(AddRefreshTokenEntity {
+            jti: claims.jti,
+            family_id: claims.family_id,
+            brawler_id,
+            expires_at: expires_at.naive_utc(),
+        })
+        .await?;
+
+    Ok(token)
 }
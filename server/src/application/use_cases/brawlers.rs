@@ -1,6 +1,11 @@
+use crate::application::errors::{AppError, AppResult};
 use crate::{
+    application::use_cases::authentication::start_session,
     domain::{
-        repositories::brawlers::BrawlerRepository,
+        repositories::{
+            brawlers::BrawlerRepository, refresh_tokens::RefreshTokenRepository,
+            sessions::SessionRepository,
+        },
         value_objects::{
             base64_image::Base64Image,
             brawler_model::{
@@ -12,32 +17,49 @@ use crate::{
     },
     infrastructure::{argon2::hash, cloudinary::UploadImageOptions, jwt::jwt_model::Passport},
 };
-use anyhow::Result;
 use std::sync::Arc;
 
-pub struct BrawlersUseCase<T>
+pub struct BrawlersUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    brawler_repository: Arc<T>,
+    brawler_repository: Arc<T1>,
+    refresh_token_repository: Arc<T2>,
+    session_repository: Arc<T3>,
 }
 
-impl<T> BrawlersUseCase<T>
+impl<T1, T2, T3> BrawlersUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    pub fn new(brawler_repository: Arc<T>) -> Self {
-        Self { brawler_repository }
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        refresh_token_repository: Arc<T2>,
+        session_repository: Arc<T3>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            refresh_token_repository,
+            session_repository,
+        }
     }
 
-    pub async fn register(&self, mut register_model: RegisterBrawlerModel) -> Result<Passport> {
+    pub async fn register(
+        &self,
+        mut register_model: RegisterBrawlerModel,
+        user_agent: Option<String>,
+    ) -> AppResult<Passport> {
         if self
             .brawler_repository
             .find_by_username(&register_model.username)
             .await
             .is_ok()
         {
-            return Err(anyhow::anyhow!("Username already taken"));
+            return Err(AppError::Conflict("Username already taken".to_string()));
         }
 
         let hashed_password = hash(register_model.password.clone())?;
@@ -48,8 +70,17 @@ where
 
         let brawler_id = self.brawler_repository.register(register_entity).await?;
 
-        let passport = Passport::new(
+        let (session_id, refresh_token) = start_session(
+            self.session_repository.as_ref(),
+            self.refresh_token_repository.as_ref(),
             brawler_id,
+            user_agent,
+        )
+        .await?;
+
+        let mut passport = Passport::new(
+            brawler_id,
+            session_id,
             register_model.display_name.clone(),
             register_model.username.clone(),
             None,
@@ -58,6 +89,7 @@ where
             0,
             0,
         );
+        passport.refresh_token = Some(refresh_token);
         Ok(passport)
     }
 
@@ -65,7 +97,7 @@ where
         &self,
         base64_image: String,
         brawler_id: i32,
-    ) -> Result<UploadedImage> {
+    ) -> AppResult<UploadedImage> {
         let option = UploadImageOptions {
             folder: Some("brawlers_avatar".to_string()),
             public_id: Some(brawler_id.to_string()),
@@ -88,12 +120,13 @@ where
         Ok(uploaded_image)
     }
 
-    pub async fn get_profile(&self, brawler_id: i32) -> Result<Passport> {
+    pub async fn get_profile(&self, brawler_id: i32, session_id: &str) -> AppResult<Passport> {
         let brawler_entity = self.brawler_repository.find_by_id(brawler_id).await?;
         let (joined_count, completed_count) = self.brawler_repository.get_stats(brawler_id).await?;
 
         let passport = Passport::new(
             brawler_entity.id,
+            session_id.to_string(),
             brawler_entity.display_name,
             brawler_entity.username,
             brawler_entity.avatar_url,
@@ -106,7 +139,10 @@ where
         Ok(passport)
     }
 
-    pub async fn get_profile_by_username(&self, username: String) -> Result<BrawlerProfileModel> {
+    pub async fn get_profile_by_username(
+        &self,
+        username: String,
+    ) -> AppResult<BrawlerProfileModel> {
         let brawler_entity = self.brawler_repository.find_by_username(&username).await?;
         let (joined_count, completed_count) =
             self.brawler_repository.get_stats(brawler_entity.id).await?;
@@ -127,7 +163,7 @@ where
         &self,
         base64_image: String,
         brawler_id: i32,
-    ) -> Result<UploadedImage> {
+    ) -> AppResult<UploadedImage> {
         let option = UploadImageOptions {
             folder: Some("brawlers_cover".to_string()),
             public_id: Some(format!("cover_{}", brawler_id)),
@@ -155,7 +191,7 @@ where
         query: &str,
         page: i64,
         page_size: i64,
-    ) -> Result<BrawlerPaginationModel> {
+    ) -> AppResult<BrawlerPaginationModel> {
         let (entities, total) = self
             .brawler_repository
             .search(Some(query.to_string()), page, page_size)
@@ -182,7 +218,7 @@ where
         })
     }
 
-    pub async fn check_username(&self, username: String) -> Result<bool> {
+    pub async fn check_username(&self, username: String) -> AppResult<bool> {
         let exists = self
             .brawler_repository
             .find_by_username(&username)
@@ -194,15 +230,18 @@ where
     pub async fn update_display_name(
         &self,
         brawler_id: i32,
+        session_id: &str,
         display_name: String,
-    ) -> Result<Passport> {
+    ) -> AppResult<Passport> {
         // Validate display name
         if display_name.trim().is_empty() {
-            return Err(anyhow::anyhow!("Display name cannot be empty"));
+            return Err(AppError::Validation(
+                "Display name cannot be empty".to_string(),
+            ));
         }
         if display_name.len() > 50 {
-            return Err(anyhow::anyhow!(
-                "Display name is too long (max 50 characters)"
+            return Err(AppError::Validation(
+                "Display name is too long (max 50 characters)".to_string(),
             ));
         }
 
@@ -212,13 +251,15 @@ where
             .await?;
 
         // Return updated passport
-        self.get_profile(brawler_id).await
+        self.get_profile(brawler_id, session_id).await
     }
 
-    pub async fn update_bio(&self, brawler_id: i32, bio: String) -> Result<BrawlerProfileModel> {
+    pub async fn update_bio(&self, brawler_id: i32, bio: String) -> AppResult<BrawlerProfileModel> {
         // Limit bio length
         if bio.len() > 500 {
-            return Err(anyhow::anyhow!("Bio is too long (max 500 characters)"));
+            return Err(AppError::Validation(
+                "Bio is too long (max 500 characters)".to_string(),
+            ));
         }
 
         // Update in database
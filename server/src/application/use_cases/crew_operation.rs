@@ -1,81 +1,87 @@
+use crate::application::{
+    errors::{AppError, AppResult},
+    use_cases::notifications::notify,
+};
 use crate::domain::{
-    entities::crew_memberships::CrewMemberShips,
+    entities::{
+        crew_memberships::CrewMemberShips,
+        mission_join_requests::{AddMissionJoinRequestEntity, MissionJoinRequestEntity},
+        missions::MissionEntity,
+    },
     repositories::{
-        crew_operation::CrewOperationRepository, mission_viewing::MissionViewingRepository,
+        crew_operation::CrewOperationRepository, mission_chat::MissionChatRepository,
+        mission_join_requests::MissionJoinRequestRepository,
+        mission_viewing::MissionViewingRepository, notifications::NotificationRepository,
+    },
+    value_objects::{
+        join_request_statuses::JoinRequestStatuses,
+        mission_join_request_model::MissionJoinRequestModel, mission_model::CurrentMissionModel,
+        mission_statuses::MissionStatuses, realtime::RealtimeEvent,
     },
-    value_objects::{mission_statuses::MissionStatuses, realtime::RealtimeEvent},
 };
 use crate::infrastructure::realtime::SharedRealtimeHub;
-use anyhow::Result;
 use std::sync::Arc;
 
-pub struct CrewOperationUseCase<T1, T2>
+pub struct CrewOperationUseCase<T1, T2, T3, T4, T5>
 where
     T1: CrewOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
 {
     crew_operation_repository: Arc<T1>,
     mission_viewing_repository: Arc<T2>,
+    notification_repository: Arc<T3>,
+    mission_chat_repository: Arc<T4>,
+    mission_join_request_repository: Arc<T5>,
     pub realtime_hub: SharedRealtimeHub,
 }
 
-impl<T1, T2> CrewOperationUseCase<T1, T2>
+impl<T1, T2, T3, T4, T5> CrewOperationUseCase<T1, T2, T3, T4, T5>
 where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
 {
     pub fn new(
         crew_operation_repository: Arc<T1>,
         mission_viewing_repository: Arc<T2>,
+        notification_repository: Arc<T3>,
+        mission_chat_repository: Arc<T4>,
+        mission_join_request_repository: Arc<T5>,
         realtime_hub: SharedRealtimeHub,
     ) -> Self {
         Self {
             crew_operation_repository,
             mission_viewing_repository,
+            notification_repository,
+            mission_chat_repository,
+            mission_join_request_repository,
             realtime_hub,
         }
     }
 
-    pub async fn join(&self, mission_id: i32, brawler_id: i32) -> Result<()> {
+    pub async fn join(&self, mission_id: i32, brawler_id: i32) -> AppResult<()> {
         let mission = self.mission_viewing_repository.get_one(mission_id).await?;
 
         if mission.chief_id == brawler_id {
-            return Err(anyhow::anyhow!(
-                "Chiefs cannot join their own missions as crew members"
+            return Err(AppError::Conflict(
+                "Chiefs cannot join their own missions as crew members".to_string(),
             ));
         }
 
-        // Check if user is already in any mission
-        let current_mission = self
-            .crew_operation_repository
-            .get_current_mission(brawler_id)
-            .await?;
-        if current_mission.is_some() {
-            return Err(anyhow::anyhow!(
-                "You are already in an active mission. Leave it first."
+        if mission.approval_required {
+            return Err(AppError::Conflict(
+                "This mission requires the chief's approval. Send a join request instead."
+                    .to_string(),
             ));
         }
 
-        let mission_status_condition = mission.status == MissionStatuses::Open.to_string()
-            || mission.status == MissionStatuses::InProgress.to_string()
-            || mission.status == MissionStatuses::Failed.to_string();
-        if !mission_status_condition {
-            return Err(anyhow::anyhow!("Mission is not joinable"));
-        }
-
-        // Check max participants
-        if mission.max_participants > 0 {
-            let crew_count = self
-                .mission_viewing_repository
-                .crew_counting(mission_id)
-                .await?;
-            if crew_count >= mission.max_participants as i64 {
-                return Err(anyhow::anyhow!(
-                    "Mission is full (Max {} members)",
-                    mission.max_participants
-                ));
-            }
-        }
+        self.ensure_not_in_mission(brawler_id).await?;
+        self.ensure_has_room(&mission).await?;
 
         self.crew_operation_repository
             .join(CrewMemberShips {
@@ -84,26 +90,33 @@ where
             })
             .await?;
 
-        self.realtime_hub.broadcast(RealtimeEvent::MissionJoined {
+        let event = RealtimeEvent::MissionJoined {
             mission_id,
             brawler_id,
-        });
+        };
+        notify(
+            self.notification_repository.as_ref(),
+            &[mission.chief_id],
+            &event,
+        )
+        .await;
+        self.realtime_hub.broadcast(event);
 
         Ok(())
     }
 
-    pub async fn leave(&self, mission_id: i32, brawler_id: i32) -> Result<()> {
+    pub async fn leave(&self, mission_id: i32, brawler_id: i32) -> AppResult<()> {
         let mission = self.mission_viewing_repository.get_one(mission_id).await?;
 
         if mission.chief_id == brawler_id {
-            return Err(anyhow::anyhow!(
-                "The mission chief cannot leave. Please delete the mission instead."
+            return Err(AppError::Conflict(
+                "The mission chief cannot leave. Please delete the mission instead.".to_string(),
             ));
         }
 
-        let leaving_condition = mission.status == MissionStatuses::Open.to_string();
-        if !leaving_condition {
-            return Err(anyhow::anyhow!("Mission is not leavable"));
+        let status: MissionStatuses = mission.status.parse()?;
+        if status != MissionStatuses::Open {
+            return Err(AppError::Conflict("Mission is not leavable".to_string()));
         }
         self.crew_operation_repository
             .leave(CrewMemberShips {
@@ -112,38 +125,60 @@ where
             })
             .await?;
 
-        self.realtime_hub.broadcast(RealtimeEvent::MissionLeft {
+        let event = RealtimeEvent::MissionLeft {
             mission_id,
             brawler_id,
-        });
+        };
+        notify(
+            self.notification_repository.as_ref(),
+            &[mission.chief_id],
+            &event,
+        )
+        .await;
+        self.realtime_hub.broadcast(event);
 
         Ok(())
     }
 
-    pub async fn get_current_mission(&self, brawler_id: i32) -> Result<Option<i32>> {
-        self.crew_operation_repository
+    pub async fn get_current_mission(&self, brawler_id: i32) -> AppResult<CurrentMissionModel> {
+        let mission_id = self
+            .crew_operation_repository
             .get_current_mission(brawler_id)
-            .await
+            .await?;
+
+        let unread_count = match mission_id {
+            Some(mission_id) => self
+                .mission_chat_repository
+                .count_unread(brawler_id, vec![mission_id])
+                .await?
+                .first()
+                .map_or(0, |count| count.unread),
+            None => 0,
+        };
+
+        Ok(CurrentMissionModel {
+            mission_id,
+            unread_count,
+        })
     }
 
-    pub async fn kick(&self, mission_id: i32, chief_id: i32, brawler_id: i32) -> Result<()> {
+    pub async fn kick(&self, mission_id: i32, chief_id: i32, brawler_id: i32) -> AppResult<()> {
         let mission = self.mission_viewing_repository.get_one(mission_id).await?;
 
         if mission.chief_id != chief_id {
-            return Err(anyhow::anyhow!("Only the mission chief can kick members"));
+            return Err(AppError::Forbidden(
+                "Only the mission chief can kick members".to_string(),
+            ));
         }
 
         if mission.chief_id == brawler_id {
-            return Err(anyhow::anyhow!("You cannot kick yourself"));
+            return Err(AppError::Validation("You cannot kick yourself".to_string()));
         }
 
-        let kicking_condition = mission.status == MissionStatuses::Open.to_string()
-            || mission.status == MissionStatuses::InProgress.to_string()
-            || mission.status == MissionStatuses::Failed.to_string();
-
-        if !kicking_condition {
-            return Err(anyhow::anyhow!(
-                "Members can only be kicked from open, in-progress or failed missions"
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.accepts_crew_changes() {
+            return Err(AppError::Conflict(
+                "Members can only be kicked from open, in-progress or failed missions".to_string(),
             ));
         }
 
@@ -154,11 +189,282 @@ where
             })
             .await?;
 
-        self.realtime_hub.broadcast(RealtimeEvent::MissionLeft {
+        // The kicked brawler is the one who needs to hear about it
+        let event = RealtimeEvent::MissionLeft {
+            mission_id,
+            brawler_id,
+        };
+        notify(self.notification_repository.as_ref(), &[brawler_id], &event).await;
+        self.realtime_hub.broadcast(event);
+
+        Ok(())
+    }
+
+    pub async fn request_to_join(&self, mission_id: i32, brawler_id: i32) -> AppResult<i32> {
+        let mission = self.mission_viewing_repository.get_one(mission_id).await?;
+
+        if !mission.approval_required {
+            return Err(AppError::Conflict(
+                "This mission doesn't require approval, join it directly".to_string(),
+            ));
+        }
+        if mission.chief_id == brawler_id {
+            return Err(AppError::Conflict(
+                "Chiefs cannot join their own missions as crew members".to_string(),
+            ));
+        }
+
+        self.ensure_not_in_mission(brawler_id).await?;
+        self.ensure_has_room(&mission).await?;
+
+        let pending = self
+            .mission_join_request_repository
+            .find_pending(mission_id, brawler_id)
+            .await?;
+        if pending.is_some() {
+            return Err(AppError::Conflict(
+                "You already asked to join this mission".to_string(),
+            ));
+        }
+
+        let request_id = self
+            .mission_join_request_repository
+            .create(AddMissionJoinRequestEntity {
+                mission_id,
+                brawler_id,
+                status: JoinRequestStatuses::Pending.to_string(),
+            })
+            .await?;
+
+        let event = RealtimeEvent::MissionJoinRequested {
+            request_id,
             mission_id,
             brawler_id,
+            chief_id: mission.chief_id,
+        };
+        notify(
+            self.notification_repository.as_ref(),
+            &[mission.chief_id],
+            &event,
+        )
+        .await;
+        self.realtime_hub.broadcast(event);
+
+        Ok(request_id)
+    }
+
+    pub async fn cancel_join_request(&self, request_id: i32, brawler_id: i32) -> AppResult<()> {
+        let request = self
+            .mission_join_request_repository
+            .get_by_id(request_id)
+            .await?;
+
+        if request.brawler_id != brawler_id {
+            return Err(AppError::Forbidden(
+                "This join request is not yours".to_string(),
+            ));
+        }
+
+        let mission = self
+            .mission_viewing_repository
+            .get_one(request.mission_id)
+            .await?;
+
+        self.close_join_request(request_id, JoinRequestStatuses::Cancelled)
+            .await?;
+
+        let event = RealtimeEvent::MissionJoinRequestCancelled {
+            request_id,
+            mission_id: request.mission_id,
+            brawler_id,
+            chief_id: mission.chief_id,
+        };
+        notify(
+            self.notification_repository.as_ref(),
+            &[mission.chief_id],
+            &event,
+        )
+        .await;
+        self.realtime_hub.broadcast(event);
+
+        Ok(())
+    }
+
+    pub async fn get_join_requests(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+    ) -> AppResult<Vec<MissionJoinRequestModel>> {
+        let mission = self.mission_viewing_repository.get_one(mission_id).await?;
+
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the mission chief can see join requests".to_string(),
+            ));
+        }
+
+        Ok(self
+            .mission_join_request_repository
+            .get_pending_for_mission(mission_id)
+            .await?)
+    }
+
+    pub async fn get_my_join_requests(
+        &self,
+        brawler_id: i32,
+    ) -> AppResult<Vec<MissionJoinRequestModel>> {
+        Ok(self
+            .mission_join_request_repository
+            .get_pending_for_brawler(brawler_id)
+            .await?)
+    }
+
+    pub async fn approve_join_request(&self, request_id: i32, chief_id: i32) -> AppResult<()> {
+        let (request, mission) = self.get_request_for_chief(request_id, chief_id).await?;
+
+        // Things may have changed since the request was sent
+        let current_mission = self
+            .crew_operation_repository
+            .get_current_mission(request.brawler_id)
+            .await?;
+        if current_mission.is_some() {
+            return Err(AppError::Conflict(
+                "This brawler is already in an active mission".to_string(),
+            ));
+        }
+        self.ensure_has_room(&mission).await?;
+
+        let approved = self
+            .mission_join_request_repository
+            .approve(request_id)
+            .await?;
+        if !approved {
+            return Err(AppError::Conflict(
+                "Join request is no longer pending".to_string(),
+            ));
+        }
+
+        let event = RealtimeEvent::MissionJoinRequestDecided {
+            request_id,
+            mission_id: request.mission_id,
+            brawler_id: request.brawler_id,
+            status: JoinRequestStatuses::Approved.to_string(),
+        };
+        notify(
+            self.notification_repository.as_ref(),
+            &[request.brawler_id],
+            &event,
+        )
+        .await;
+        self.realtime_hub.broadcast(event);
+
+        // Mission boards refresh the crew count on this
+        self.realtime_hub.broadcast(RealtimeEvent::MissionJoined {
+            mission_id: request.mission_id,
+            brawler_id: request.brawler_id,
         });
 
         Ok(())
     }
+
+    pub async fn reject_join_request(&self, request_id: i32, chief_id: i32) -> AppResult<()> {
+        let (request, _) = self.get_request_for_chief(request_id, chief_id).await?;
+
+        self.close_join_request(request_id, JoinRequestStatuses::Rejected)
+            .await?;
+
+        let event = RealtimeEvent::MissionJoinRequestDecided {
+            request_id,
+            mission_id: request.mission_id,
+            brawler_id: request.brawler_id,
+            status: JoinRequestStatuses::Rejected.to_string(),
+        };
+        notify(
+            self.notification_repository.as_ref(),
+            &[request.brawler_id],
+            &event,
+        )
+        .await;
+        self.realtime_hub.broadcast(event);
+
+        Ok(())
+    }
+
+    async fn get_request_for_chief(
+        &self,
+        request_id: i32,
+        chief_id: i32,
+    ) -> AppResult<(MissionJoinRequestEntity, MissionEntity)> {
+        let request = self
+            .mission_join_request_repository
+            .get_by_id(request_id)
+            .await?;
+        let mission = self
+            .mission_viewing_repository
+            .get_one(request.mission_id)
+            .await?;
+
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the mission chief can answer join requests".to_string(),
+            ));
+        }
+
+        Ok((request, mission))
+    }
+
+    async fn close_join_request(
+        &self,
+        request_id: i32,
+        status: JoinRequestStatuses,
+    ) -> AppResult<()> {
+        let closed = self
+            .mission_join_request_repository
+            .close(request_id, status)
+            .await?;
+        if !closed {
+            return Err(AppError::Conflict(
+                "Join request is no longer pending".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_not_in_mission(&self, brawler_id: i32) -> AppResult<()> {
+        let current_mission = self
+            .crew_operation_repository
+            .get_current_mission(brawler_id)
+            .await?;
+        if current_mission.is_some() {
+            return Err(AppError::Conflict(
+                "You are already in an active mission. Leave it first.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Shared by direct joins and approved join requests
+    async fn ensure_has_room(&self, mission: &MissionEntity) -> AppResult<()> {
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.accepts_crew_changes() {
+            return Err(AppError::Conflict("Mission is not joinable".to_string()));
+        }
+
+        if mission.max_participants > 0 {
+            let crew_count = self
+                .mission_viewing_repository
+                .crew_counting(mission.id)
+                .await?;
+            if crew_count >= mission.max_participants as i64 {
+                return Err(AppError::Conflict(format!(
+                    "Mission is full (Max {} members)",
+                    mission.max_participants
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
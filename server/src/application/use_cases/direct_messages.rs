@@ -0,0 +1,253 @@
+use crate::application::errors::{AppError, AppResult};
+use chrono::Utc;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    domain::{
+        entities::direct_messages::{
+            AddDirectConversationEntity, AddDirectMessageEntity, DirectConversationEntity,
+        },
+        repositories::{
+            brawlers::BrawlerRepository, direct_messages::DirectMessageRepository,
+            friendships::FriendshipRepository,
+        },
+        value_objects::{
+            direct_message_model::{
+                DirectConversationModel, DirectMessageCursor, DirectMessageModel,
+                DirectMessagePageModel,
+            },
+            realtime::RealtimeEvent,
+        },
+    },
+    infrastructure::realtime::SharedRealtimeHub,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 100;
+
+pub struct DirectMessageUseCase<T1, T2, T3>
+where
+    T1: DirectMessageRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+{
+    direct_message_repo: Arc<T1>,
+    friendship_repo: Arc<T2>,
+    brawler_repo: Arc<T3>,
+    realtime_hub: SharedRealtimeHub,
+}
+
+impl<T1, T2, T3> DirectMessageUseCase<T1, T2, T3>
+where
+    T1: DirectMessageRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+{
+    pub fn new(
+        direct_message_repo: Arc<T1>,
+        friendship_repo: Arc<T2>,
+        brawler_repo: Arc<T3>,
+        realtime_hub: SharedRealtimeHub,
+    ) -> Self {
+        Self {
+            direct_message_repo,
+            friendship_repo,
+            brawler_repo,
+            realtime_hub,
+        }
+    }
+
+    async fn are_friends(&self, brawler_id: i32, other_id: i32) -> AppResult<bool> {
+        let friendship = self
+            .friendship_repo
+            .check_friendship(brawler_id, other_id)
+            .await?;
+
+        Ok(friendship.is_some_and(|f| f.status == "accepted"))
+    }
+
+    pub async fn send_message(
+        &self,
+        sender_id: i32,
+        friend_id: i32,
+        content: String,
+    ) -> AppResult<DirectMessageModel> {
+        if content.trim().is_empty() {
+            return Err(AppError::Validation("Message cannot be empty".to_string()));
+        }
+
+        if !self.are_friends(sender_id, friend_id).await? {
+            // Past conversations stay readable, they just can't continue
+            return Err(AppError::Forbidden(
+                "You can only send messages to friends".to_string(),
+            ));
+        }
+
+        let conversation = self
+            .direct_message_repo
+            .get_or_create_conversation(AddDirectConversationEntity::between(sender_id, friend_id))
+            .await?;
+
+        let message = self
+            .direct_message_repo
+            .save_message(AddDirectMessageEntity {
+                conversation_id: conversation.id,
+                sender_id,
+                content,
+                created_at: Utc::now().naive_utc(),
+            })
+            .await?;
+
+        let sender = self.brawler_repo.find_by_id(sender_id).await?;
+        self.realtime_hub.broadcast(RealtimeEvent::DirectMessage {
+            conversation_id: conversation.id,
+            message_id: message.id,
+            sender_id,
+            sender_name: sender.display_name,
+            recipient_id: friend_id,
+            content: message.content.clone(),
+            created_at: message.created_at.and_utc(),
+        });
+
+        Ok(message.to_model())
+    }
+
+    pub async fn get_messages(
+        &self,
+        brawler_id: i32,
+        friend_id: i32,
+        cursor: DirectMessageCursor,
+    ) -> AppResult<DirectMessagePageModel> {
+        let read_only = !self.are_friends(brawler_id, friend_id).await?;
+
+        let conversation = match self
+            .direct_message_repo
+            .find_conversation(brawler_id, friend_id)
+            .await?
+        {
+            Some(conversation) => conversation,
+            None if read_only => {
+                return Err(AppError::Forbidden(
+                    "You can only message friends".to_string(),
+                ));
+            }
+            None => {
+                return Ok(DirectMessagePageModel {
+                    messages: Vec::new(),
+                    next_cursor: None,
+                    read_only,
+                });
+            }
+        };
+
+        let limit = cursor
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        // One extra row tells us whether another page exists
+        let mut messages = self
+            .direct_message_repo
+            .get_messages(conversation.id, &cursor, limit + 1)
+            .await?;
+
+        let has_more = messages.len() as i64 > limit;
+        if has_more {
+            messages.remove(0);
+        }
+        let next_cursor = messages.first().filter(|_| has_more).map(|m| m.id);
+
+        Ok(DirectMessagePageModel {
+            messages: messages.iter().map(|m| m.to_model()).collect(),
+            next_cursor,
+            read_only,
+        })
+    }
+
+    // Most recently active first
+    pub async fn get_conversations(
+        &self,
+        brawler_id: i32,
+    ) -> AppResult<Vec<DirectConversationModel>> {
+        let conversations = self
+            .direct_message_repo
+            .get_conversations(brawler_id)
+            .await?;
+        if conversations.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conversation_ids: Vec<i32> = conversations.iter().map(|c| c.id).collect();
+        let mut last_messages: HashMap<i32, DirectMessageModel> = self
+            .direct_message_repo
+            .get_last_messages(conversation_ids.clone())
+            .await?
+            .iter()
+            .map(|m| (m.conversation_id, m.to_model()))
+            .collect();
+        let unread: HashMap<i32, i64> = self
+            .direct_message_repo
+            .count_unread(brawler_id, conversation_ids)
+            .await?
+            .into_iter()
+            .map(|count| (count.conversation_id, count.unread))
+            .collect();
+
+        let mut result = Vec::new();
+        for conversation in conversations {
+            let friend_id = conversation.other(brawler_id);
+            let friend = match self.brawler_repo.find_by_id(friend_id).await {
+                Ok(friend) => friend,
+                Err(_) => continue,
+            };
+
+            result.push(DirectConversationModel {
+                conversation_id: conversation.id,
+                friend_id,
+                display_name: friend.display_name,
+                username: friend.username,
+                avatar_url: friend.avatar_url,
+                last_message: last_messages.remove(&conversation.id),
+                unread_count: unread.get(&conversation.id).copied().unwrap_or(0),
+                read_only: !self.are_friends(brawler_id, friend_id).await?,
+            });
+        }
+
+        result.sort_by_key(|c| std::cmp::Reverse(c.last_message.as_ref().map(|m| m.id)));
+
+        Ok(result)
+    }
+
+    pub async fn mark_read(&self, brawler_id: i32, friend_id: i32) -> AppResult<()> {
+        let conversation = self.find_conversation(brawler_id, friend_id).await?;
+        let read_at = Utc::now();
+
+        let updated = self
+            .direct_message_repo
+            .mark_read(conversation.id, brawler_id, read_at.naive_utc())
+            .await?;
+
+        if updated > 0 {
+            self.realtime_hub
+                .broadcast(RealtimeEvent::DirectMessagesRead {
+                    conversation_id: conversation.id,
+                    reader_id: brawler_id,
+                    sender_id: friend_id,
+                    read_at,
+                });
+        }
+
+        Ok(())
+    }
+
+    async fn find_conversation(
+        &self,
+        brawler_id: i32,
+        friend_id: i32,
+    ) -> AppResult<DirectConversationEntity> {
+        self.direct_message_repo
+            .find_conversation(brawler_id, friend_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Conversation not found".to_string()))
+    }
+}
@@ -1,39 +1,75 @@
-use anyhow::{Result, bail};
+use crate::application::{
+    errors::{AppError, AppResult},
+    use_cases::notifications::notify,
+};
 use chrono::Utc;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::{
     domain::{
-        entities::mission_chat::{MissionChatMessageWithBrawler, NewMissionChatMessageEntity},
+        entities::{
+            brawlers::BrawlerEntity,
+            mission_chat::{
+                AddMissionChatMentionEntity, AddMissionChatPinEntity, AddMissionChatReactionEntity,
+                MissionChatMessageEntity, MissionChatMessageWithBrawler,
+                NewMissionChatMessageEntity,
+            },
+        },
         repositories::{
             brawlers::BrawlerRepository, crew_operation::CrewOperationRepository,
             mission_chat::MissionChatRepository, mission_viewing::MissionViewingRepository,
+            notifications::NotificationRepository,
+        },
+        value_objects::{
+            base64_image::Base64Image,
+            brawler_model::PaginationModel,
+            mission_chat_model::{
+                MissionChatCursor, MissionChatMessageEditModel, MissionChatPageModel,
+                MissionChatSearchModel, MissionChatThreadModel, PinnedMessageModel,
+                QuotedMessageModel, ReactionSummaryModel, ReadPositionModel,
+            },
+            realtime::RealtimeEvent,
         },
-        value_objects::realtime::RealtimeEvent,
     },
-    infrastructure::realtime::RealtimeHub,
+    infrastructure::{cloudinary::UploadImageOptions, realtime::RealtimeHub},
 };
 
-pub struct MissionChatUseCase<T1, T2, T3, T4> {
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 100;
+// Bytes, enough for flag and skin tone sequences
+const MAX_EMOJI_LENGTH: usize = 32;
+const SNIPPET_CHARS: usize = 120;
+const MAX_MENTIONS: usize = 10;
+const MAX_PINS_PER_MISSION: i64 = 10;
+const MAX_SEARCH_QUERY_CHARS: usize = 200;
+// At most one typing event per brawler per mission in this window
+const TYPING_THROTTLE: Duration = Duration::from_secs(3);
+// Clients drop the indicator once this passes without a fresh event
+const TYPING_EXPIRY_SECONDS: i64 = 6;
+
+pub struct MissionChatUseCase<T1, T2, T3, T4, T5> {
     mission_chat_repo: Arc<T1>,
     crew_repo: Arc<T2>,
     mission_view_repo: Arc<T3>,
     brawler_repo: Arc<T4>,
+    notification_repo: Arc<T5>,
     realtime_hub: Arc<RealtimeHub>,
 }
 
-impl<T1, T2, T3, T4> MissionChatUseCase<T1, T2, T3, T4>
+impl<T1, T2, T3, T4, T5> MissionChatUseCase<T1, T2, T3, T4, T5>
 where
     T1: MissionChatRepository + Send + Sync,
     T2: CrewOperationRepository + Send + Sync,
     T3: MissionViewingRepository + Send + Sync,
     T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
 {
     pub fn new(
         mission_chat_repo: Arc<T1>,
         crew_repo: Arc<T2>,
         mission_view_repo: Arc<T3>,
         brawler_repo: Arc<T4>,
+        notification_repo: Arc<T5>,
         realtime_hub: Arc<RealtimeHub>,
     ) -> Self {
         Self {
@@ -41,34 +77,84 @@ where
             crew_repo,
             mission_view_repo,
             brawler_repo,
+            notification_repo,
             realtime_hub,
         }
     }
 
+    // Members and the chief may read and write the mission chat
+    pub async fn ensure_participant(&self, mission_id: i32, brawler_id: i32) -> AppResult<()> {
+        let mission = self.mission_view_repo.get_one(mission_id).await?;
+
+        if !self
+            .is_participant(mission_id, mission.chief_id, brawler_id)
+            .await?
+        {
+            return Err(AppError::Forbidden(
+                "You are not a member of this mission".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn is_participant(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<bool> {
+        if chief_id == brawler_id {
+            return Ok(true);
+        }
+
+        Ok(self.crew_repo.is_member(mission_id, brawler_id).await?)
+    }
+
     pub async fn send_message(
         &self,
         mission_id: i32,
         brawler_id: i32,
         content: String,
-    ) -> Result<i32> {
-        // Authorization: Check if user is in mission
-        let is_member = self.crew_repo.is_member(mission_id, brawler_id).await?;
-        let mission = self.mission_view_repo.get_one(mission_id).await?;
-        let is_chief = mission.chief_id == brawler_id;
+        image: Option<String>,
+        reply_to_id: Option<i32>,
+    ) -> AppResult<i32> {
+        if content.trim().is_empty() && image.is_none() {
+            return Err(AppError::Validation(
+                "Message must have text or an image".to_string(),
+            ));
+        }
+
+        self.ensure_participant(mission_id, brawler_id).await?;
 
-        if !is_member && !is_chief {
-            bail!("You are not a member of this mission");
+        if let Some(reply_to_id) = reply_to_id {
+            let parent = self.mission_chat_repo.get_message(reply_to_id).await?;
+            if parent.mission_id != mission_id {
+                return Err(AppError::Validation(
+                    "Replies must stay within the same mission".to_string(),
+                ));
+            }
+            if parent.deleted_at.is_some() {
+                return Err(AppError::NotFound("Message not found".to_string()));
+            }
         }
 
         let brawler = self.brawler_repo.find_by_id(brawler_id).await?;
 
+        // Upload only once we know the sender may post here
+        let image_url = match image {
+            Some(image) => Some(self.upload_image(mission_id, brawler_id, image).await?),
+            None => None,
+        };
+
         let now = Utc::now();
         let new_message = NewMissionChatMessageEntity {
             mission_id,
             brawler_id,
             content: content.clone(),
             created_at: now.naive_utc(),
-            image_url: None,
+            image_url: image_url.clone(),
+            reply_to_id,
         };
 
         let message_id = self.mission_chat_repo.save_message(new_message).await?;
@@ -77,31 +163,919 @@ where
         self.realtime_hub
             .broadcast(RealtimeEvent::MissionChatMessage {
                 mission_id,
+                message_id,
                 brawler_id,
-                brawler_name: brawler.display_name,
-                content,
+                brawler_name: brawler.display_name.clone(),
+                content: content.clone(),
                 created_at: now,
+                image_url,
+                reply_to_id,
             });
 
+        // The message is already out, a failed mention shouldn't undo that
+        if let Err(e) = self
+            .notify_mentions(mission_id, message_id, &brawler, &content)
+            .await
+        {
+            tracing::error!(
+                "Failed to deliver mentions of message {}: {:?}",
+                message_id,
+                e
+            );
+        }
+
         Ok(message_id)
     }
 
+    async fn notify_mentions(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        author: &BrawlerEntity,
+        content: &str,
+    ) -> AppResult<()> {
+        let usernames = parse_mentions(content);
+        if usernames.is_empty() {
+            return Ok(());
+        }
+
+        let mission = self.mission_view_repo.get_one(mission_id).await?;
+
+        let mut mentioned_ids: Vec<i32> = Vec::new();
+        for username in usernames {
+            // Unknown names are just text that happens to start with @
+            let Ok(brawler) = self.brawler_repo.find_by_username(&username).await else {
+                continue;
+            };
+
+            if !brawler.username.eq_ignore_ascii_case(&username)
+                || brawler.id == author.id
+                || mentioned_ids.contains(&brawler.id)
+                || !self
+                    .is_participant(mission_id, mission.chief_id, brawler.id)
+                    .await?
+            {
+                continue;
+            }
+            mentioned_ids.push(brawler.id);
+        }
+        if mentioned_ids.is_empty() {
+            return Ok(());
+        }
+
+        self.mission_chat_repo
+            .add_mentions(
+                mentioned_ids
+                    .iter()
+                    .map(|brawler_id| AddMissionChatMentionEntity {
+                        message_id,
+                        brawler_id: *brawler_id,
+                    })
+                    .collect(),
+            )
+            .await?;
+
+        let snippet = snippet_of(content);
+        for brawler_id in mentioned_ids {
+            let event = RealtimeEvent::Mentioned {
+                mission_id,
+                message_id,
+                brawler_id,
+                mentioned_by: author.id,
+                mentioned_by_name: author.display_name.clone(),
+                snippet: snippet.clone(),
+            };
+
+            notify(self.notification_repo.as_ref(), &[brawler_id], &event).await;
+            self.realtime_hub.broadcast(event);
+        }
+
+        Ok(())
+    }
+
     pub async fn get_messages(
         &self,
         mission_id: i32,
         brawler_id: i32,
-    ) -> Result<Vec<MissionChatMessageWithBrawler>> {
-        // Authorization: Check if user is in mission
-        let is_member = self.crew_repo.is_member(mission_id, brawler_id).await?;
+        cursor: MissionChatCursor,
+    ) -> AppResult<MissionChatPageModel> {
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        let (messages, next_cursor) = self
+            .load_page(mission_id, None, brawler_id, &cursor)
+            .await?;
+
+        Ok(MissionChatPageModel {
+            messages,
+            next_cursor,
+        })
+    }
+
+    pub async fn get_thread(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+        cursor: MissionChatCursor,
+    ) -> AppResult<MissionChatThreadModel> {
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        // A deleted parent still anchors its thread, as a tombstone
+        let mut parent = match self
+            .mission_chat_repo
+            .get_messages_by_ids(vec![message_id])
+            .await?
+            .pop()
+        {
+            Some(parent) if parent.mission_id == mission_id => parent,
+            _ => return Err(AppError::NotFound("Message not found".to_string())),
+        };
+        self.decorate(std::slice::from_mut(&mut parent), brawler_id)
+            .await?;
+
+        let (replies, next_cursor) = self
+            .load_page(mission_id, Some(message_id), brawler_id, &cursor)
+            .await?;
+
+        Ok(MissionChatThreadModel {
+            parent,
+            replies,
+            next_cursor,
+        })
+    }
+
+    pub async fn search_messages(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        query: &str,
+        page: i64,
+        page_size: i64,
+    ) -> AppResult<MissionChatSearchModel> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(AppError::Validation(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+        if query.chars().count() > MAX_SEARCH_QUERY_CHARS {
+            return Err(AppError::Validation(format!(
+                "Search query cannot be longer than {} characters",
+                MAX_SEARCH_QUERY_CHARS
+            )));
+        }
+
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        let page = page.max(1);
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+
+        let (items, total) = self
+            .mission_chat_repo
+            .search_messages(mission_id, query.to_string(), page, page_size)
+            .await?;
+
+        Ok(MissionChatSearchModel {
+            pagination: PaginationModel {
+                current_page: page,
+                page_size,
+                length: total,
+            },
+            items,
+        })
+    }
+
+    async fn load_page(
+        &self,
+        mission_id: i32,
+        reply_to_id: Option<i32>,
+        brawler_id: i32,
+        cursor: &MissionChatCursor,
+    ) -> AppResult<(Vec<MissionChatMessageWithBrawler>, Option<i32>)> {
+        if cursor.before.is_some() && cursor.after.is_some() {
+            return Err(AppError::Validation(
+                "Use either before or after, not both".to_string(),
+            ));
+        }
+
+        let limit = cursor
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        // One extra row tells us whether another page exists
+        let mut messages = self
+            .mission_chat_repo
+            .get_messages_by_mission(mission_id, reply_to_id, cursor, limit + 1)
+            .await?;
+
+        let next_cursor = trim_page(&mut messages, limit, cursor.after.is_some(), |m| m.id);
+
+        self.decorate(&mut messages, brawler_id).await?;
+
+        Ok((messages, next_cursor))
+    }
+
+    pub async fn edit_message(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+        content: String,
+    ) -> AppResult<()> {
+        let message = self.find_live_message(mission_id, message_id).await?;
+
+        if message.brawler_id != brawler_id {
+            return Err(AppError::Forbidden(
+                "You can only edit your own messages".to_string(),
+            ));
+        }
+        if content.trim().is_empty() && message.image_url.is_none() {
+            return Err(AppError::Validation(
+                "Message must have text or an image".to_string(),
+            ));
+        }
+
+        // Former members keep their history but can't rewrite it
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        if content == message.content {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        self.mission_chat_repo
+            .edit_message(message_id, content.clone(), now.naive_utc())
+            .await?;
+
+        self.realtime_hub
+            .broadcast(RealtimeEvent::MissionChatMessageEdited {
+                mission_id,
+                message_id,
+                content,
+                edited_at: now,
+            });
+
+        Ok(())
+    }
+
+    // Authors retract their own messages, the chief moderates everyone's
+    pub async fn delete_message(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<()> {
+        let message = self.find_live_message(mission_id, message_id).await?;
         let mission = self.mission_view_repo.get_one(mission_id).await?;
-        let is_chief = mission.chief_id == brawler_id;
 
-        if !is_member && !is_chief {
-            bail!("You are not a member of this mission");
+        if mission.chief_id != brawler_id {
+            if message.brawler_id != brawler_id {
+                return Err(AppError::Forbidden(
+                    "You can only delete your own messages".to_string(),
+                ));
+            }
+            self.ensure_participant(mission_id, brawler_id).await?;
         }
 
         self.mission_chat_repo
-            .get_messages_by_mission(mission_id)
-            .await
+            .delete_message(message_id, Utc::now().naive_utc())
+            .await?;
+
+        self.realtime_hub
+            .broadcast(RealtimeEvent::MissionChatMessageDeleted {
+                mission_id,
+                message_id,
+                deleted_by: brawler_id,
+            });
+
+        // A pin on a tombstone would just take up one of the slots
+        if self.mission_chat_repo.unpin_message(message_id).await? {
+            self.realtime_hub
+                .broadcast(RealtimeEvent::MissionPinsChanged {
+                    mission_id,
+                    message_id,
+                    pinned: false,
+                    brawler_id,
+                });
+        }
+
+        Ok(())
+    }
+
+    pub async fn pin_message(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<()> {
+        self.ensure_chief(mission_id, brawler_id).await?;
+        self.find_live_message(mission_id, message_id).await?;
+
+        let pinned = self
+            .mission_chat_repo
+            .pin_message(
+                AddMissionChatPinEntity {
+                    message_id,
+                    mission_id,
+                    pinned_by: brawler_id,
+                },
+                MAX_PINS_PER_MISSION,
+            )
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict(format!(
+                    "A mission can have at most {} pinned messages",
+                    MAX_PINS_PER_MISSION
+                ))
+            })?;
+
+        if pinned {
+            self.realtime_hub
+                .broadcast(RealtimeEvent::MissionPinsChanged {
+                    mission_id,
+                    message_id,
+                    pinned: true,
+                    brawler_id,
+                });
+        }
+
+        Ok(())
+    }
+
+    pub async fn unpin_message(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<()> {
+        self.ensure_chief(mission_id, brawler_id).await?;
+
+        let message = self.mission_chat_repo.get_message(message_id).await?;
+        if message.mission_id != mission_id {
+            return Err(AppError::NotFound("Message not found".to_string()));
+        }
+
+        if self.mission_chat_repo.unpin_message(message_id).await? {
+            self.realtime_hub
+                .broadcast(RealtimeEvent::MissionPinsChanged {
+                    mission_id,
+                    message_id,
+                    pinned: false,
+                    brawler_id,
+                });
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_pinned_messages(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<Vec<PinnedMessageModel>> {
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        let pins = self.mission_chat_repo.get_pins(mission_id).await?;
+        if pins.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut messages = self
+            .mission_chat_repo
+            .get_messages_by_ids(pins.iter().map(|pin| pin.message_id).collect())
+            .await?;
+        self.decorate(&mut messages, brawler_id).await?;
+
+        let mut messages: HashMap<i32, MissionChatMessageWithBrawler> = messages
+            .into_iter()
+            .map(|message| (message.id, message))
+            .collect();
+
+        Ok(pins
+            .iter()
+            .filter_map(|pin| {
+                messages
+                    .remove(&pin.message_id)
+                    .map(|message| pin.to_model(message))
+            })
+            .collect())
+    }
+
+    async fn ensure_chief(&self, mission_id: i32, brawler_id: i32) -> AppResult<()> {
+        let mission = self.mission_view_repo.get_one(mission_id).await?;
+
+        if mission.chief_id != brawler_id {
+            return Err(AppError::Forbidden(
+                "Only the mission chief can pin messages".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_message_edits(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<Vec<MissionChatMessageEditModel>> {
+        self.ensure_participant(mission_id, brawler_id).await?;
+        self.find_live_message(mission_id, message_id).await?;
+
+        let edits = self.mission_chat_repo.get_message_edits(message_id).await?;
+
+        Ok(edits.iter().map(|edit| edit.to_model()).collect())
+    }
+
+    pub async fn add_reaction(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+        emoji: String,
+    ) -> AppResult<()> {
+        let emoji = validate_emoji(emoji)?;
+        self.ensure_participant(mission_id, brawler_id).await?;
+        self.find_live_message(mission_id, message_id).await?;
+
+        let added = self
+            .mission_chat_repo
+            .add_reaction(AddMissionChatReactionEntity {
+                message_id,
+                brawler_id,
+                emoji: emoji.clone(),
+            })
+            .await?;
+
+        if added {
+            self.announce_reaction(mission_id, message_id, brawler_id, emoji, true)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_reaction(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+        emoji: String,
+    ) -> AppResult<()> {
+        let emoji = validate_emoji(emoji)?;
+        self.ensure_participant(mission_id, brawler_id).await?;
+        self.find_live_message(mission_id, message_id).await?;
+
+        let removed = self
+            .mission_chat_repo
+            .remove_reaction(message_id, brawler_id, emoji.clone())
+            .await?;
+
+        if removed {
+            self.announce_reaction(mission_id, message_id, brawler_id, emoji, false)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn mark_read(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        message_id: i32,
+    ) -> AppResult<()> {
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        // Deleted messages are still valid positions, they keep their place
+        let message = self.mission_chat_repo.get_message(message_id).await?;
+        if message.mission_id != mission_id {
+            return Err(AppError::NotFound("Message not found".to_string()));
+        }
+
+        let last_read_message_id = self
+            .mission_chat_repo
+            .mark_read(mission_id, brawler_id, message_id)
+            .await?;
+
+        // Stale marks from a lagging tab leave the position where it was
+        if last_read_message_id == message_id {
+            self.realtime_hub.broadcast(RealtimeEvent::MissionChatRead {
+                mission_id,
+                brawler_id,
+                last_read_message_id,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_read_positions(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<Vec<ReadPositionModel>> {
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        let positions = self
+            .mission_chat_repo
+            .get_read_positions(mission_id)
+            .await?;
+
+        Ok(positions
+            .iter()
+            .map(|position| position.to_model())
+            .collect())
+    }
+
+    // Carries the new total so clients don't have to count themselves
+    async fn announce_reaction(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+        emoji: String,
+        added: bool,
+    ) -> AppResult<()> {
+        let count = self
+            .mission_chat_repo
+            .count_reactions(message_id, emoji.clone())
+            .await?;
+
+        self.realtime_hub
+            .broadcast(RealtimeEvent::MissionChatReactionChanged {
+                mission_id,
+                message_id,
+                brawler_id,
+                emoji,
+                added,
+                count,
+            });
+
+        Ok(())
+    }
+
+    async fn decorate(
+        &self,
+        messages: &mut [MissionChatMessageWithBrawler],
+        brawler_id: i32,
+    ) -> AppResult<()> {
+        self.attach_quotes(messages).await?;
+        self.attach_reactions(messages, brawler_id).await
+    }
+
+    async fn attach_quotes(&self, messages: &mut [MissionChatMessageWithBrawler]) -> AppResult<()> {
+        let mut parent_ids: Vec<i32> = messages
+            .iter()
+            .filter(|message| message.deleted_at.is_none())
+            .filter_map(|message| message.reply_to_id)
+            .collect();
+        parent_ids.sort_unstable();
+        parent_ids.dedup();
+        if parent_ids.is_empty() {
+            return Ok(());
+        }
+
+        let quotes: HashMap<i32, QuotedMessageModel> = self
+            .mission_chat_repo
+            .get_messages_by_ids(parent_ids)
+            .await?
+            .iter()
+            .map(|parent| (parent.id, quote(parent)))
+            .collect();
+
+        for message in messages.iter_mut() {
+            if message.deleted_at.is_none()
+                && let Some(reply_to_id) = message.reply_to_id
+            {
+                message.reply_to = quotes.get(&reply_to_id).cloned();
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn attach_reactions(
+        &self,
+        messages: &mut [MissionChatMessageWithBrawler],
+        brawler_id: i32,
+    ) -> AppResult<()> {
+        let message_ids: Vec<i32> = messages
+            .iter()
+            .filter(|message| message.deleted_at.is_none())
+            .map(|message| message.id)
+            .collect();
+        if message_ids.is_empty() {
+            return Ok(());
+        }
+
+        let reactions = self.mission_chat_repo.get_reactions(message_ids).await?;
+
+        // Emojis keep the order they were first used in on each message
+        let mut summaries: HashMap<i32, Vec<ReactionSummaryModel>> = HashMap::new();
+        for reaction in reactions {
+            let message_summaries = summaries.entry(reaction.message_id).or_default();
+            let mine = reaction.brawler_id == brawler_id;
+
+            match message_summaries
+                .iter_mut()
+                .find(|summary| summary.emoji == reaction.emoji)
+            {
+                Some(summary) => {
+                    summary.count += 1;
+                    summary.reacted_by_me |= mine;
+                }
+                None => message_summaries.push(ReactionSummaryModel {
+                    emoji: reaction.emoji,
+                    count: 1,
+                    reacted_by_me: mine,
+                }),
+            }
+        }
+
+        for message in messages.iter_mut() {
+            if let Some(reactions) = summaries.remove(&message.id) {
+                message.reactions = reactions;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Messages of another mission or already deleted are treated as missing
+    async fn find_live_message(
+        &self,
+        mission_id: i32,
+        message_id: i32,
+    ) -> AppResult<MissionChatMessageEntity> {
+        let message = self.mission_chat_repo.get_message(message_id).await?;
+
+        if message.mission_id != mission_id || message.deleted_at.is_some() {
+            return Err(AppError::NotFound("Message not found".to_string()));
+        }
+
+        Ok(message)
+    }
+
+    async fn upload_image(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        base64_image: String,
+    ) -> AppResult<String> {
+        let option = UploadImageOptions {
+            folder: Some("mission_chat".to_string()),
+            public_id: Some(format!(
+                "chat_{}_{}_{}",
+                mission_id,
+                brawler_id,
+                Utc::now().timestamp_millis()
+            )),
+            transformation: Some("c_limit,w_1280,h_1280".to_string()),
+        };
+
+        let base64_image =
+            Base64Image::new(&base64_image).map_err(|e| AppError::Validation(e.to_string()))?;
+
+        let uploaded_image =
+            crate::infrastructure::cloudinary::upload(base64_image, option).await?;
+
+        Ok(uploaded_image.url)
+    }
+
+    pub async fn typing(&self, mission_id: i32, brawler_id: i32) -> AppResult<()> {
+        self.ensure_participant(mission_id, brawler_id).await?;
+
+        // Only a brawler who passed the membership check gets throttled, so a
+        // quiet Ok here never tells an outsider anything
+        if !self
+            .realtime_hub
+            .try_claim_typing(mission_id, brawler_id, TYPING_THROTTLE)
+        {
+            return Ok(());
+        }
+
+        let expires_at = Utc::now() + chrono::Duration::seconds(TYPING_EXPIRY_SECONDS);
+        self.realtime_hub.broadcast(RealtimeEvent::MissionTyping {
+            mission_id,
+            brawler_id,
+            expires_at,
+        });
+
+        Ok(())
+    }
+}
+
+// Pages are oldest first, so the extra row is the newest one when paging forward
+// and the oldest one otherwise. Returns the cursor for the following page, if any.
+fn trim_page<T>(
+    items: &mut Vec<T>,
+    limit: i64,
+    after: bool,
+    id: impl Fn(&T) -> i32,
+) -> Option<i32> {
+    let has_more = items.len() as i64 > limit;
+    if after {
+        items.truncate(limit as usize);
+        items.last().filter(|_| has_more).map(id)
+    } else {
+        if has_more {
+            items.remove(0);
+        }
+        items.first().filter(|_| has_more).map(id)
+    }
+}
+
+fn validate_emoji(emoji: String) -> AppResult<String> {
+    let emoji = emoji.trim().to_string();
+
+    // Plain text would turn reactions back into "+1" replies
+    if emoji.is_empty()
+        || emoji.len() > MAX_EMOJI_LENGTH
+        || emoji.is_ascii()
+        || emoji.chars().any(char::is_whitespace)
+    {
+        return Err(AppError::Validation(
+            "Reaction must be a single emoji".to_string(),
+        ));
+    }
+
+    Ok(emoji)
+}
+
+fn quote(parent: &MissionChatMessageWithBrawler) -> QuotedMessageModel {
+    QuotedMessageModel {
+        id: parent.id,
+        brawler_id: parent.brawler_id,
+        brawler_name: parent.brawler_name.clone(),
+        snippet: snippet_of(&parent.content),
+        has_image: parent.image_url.is_some(),
+        deleted: parent.deleted_at.is_some(),
+    }
+}
+
+fn snippet_of(content: &str) -> String {
+    let mut snippet: String = content.chars().take(SNIPPET_CHARS).collect();
+    if content.chars().count() > SNIPPET_CHARS {
+        snippet.push('…');
+    }
+
+    snippet
+}
+
+// `@name` at the start of the text or after anything that can't be part of a
+// name, so email addresses don't count
+fn parse_mentions(content: &str) -> Vec<String> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-';
+
+    let mut usernames: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if c == '@' && !previous.is_some_and(is_name_char) {
+            let rest = &content[index + 1..];
+            let length = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+            // A trailing full stop ends the sentence, not the name
+            let username = rest[..length].trim_end_matches('.');
+
+            if !username.is_empty()
+                && !usernames
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(username))
+            {
+                usernames.push(username.to_string());
+                if usernames.len() == MAX_MENTIONS {
+                    break;
+                }
+            }
+
+            while chars.next_if(|(_, c)| is_name_char(*c)).is_some() {}
+            previous = rest[..length].chars().last();
+            continue;
+        }
+        previous = Some(c);
+    }
+
+    usernames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_EMOJI_LENGTH, MAX_MENTIONS, parse_mentions, trim_page, validate_emoji};
+
+    #[test]
+    fn exact_page_has_no_next_cursor() {
+        let mut ids = vec![1, 2, 3];
+        assert_eq!(trim_page(&mut ids, 3, false, |id| *id), None);
+        assert_eq!(ids, [1, 2, 3]);
+
+        let mut ids = vec![1, 2, 3];
+        assert_eq!(trim_page(&mut ids, 3, true, |id| *id), None);
+        assert_eq!(ids, [1, 2, 3]);
+    }
+
+    #[test]
+    fn short_page_has_no_next_cursor() {
+        let mut ids = vec![1, 2];
+        assert_eq!(trim_page(&mut ids, 3, false, |id| *id), None);
+        assert_eq!(ids, [1, 2]);
+
+        let mut ids: Vec<i32> = Vec::new();
+        assert_eq!(trim_page(&mut ids, 3, true, |id| *id), None);
+        assert!(ids.is_empty());
+    }
+
+    #[test]
+    fn older_pages_drop_the_oldest_extra_row() {
+        let mut ids = vec![1, 2, 3, 4];
+        assert_eq!(trim_page(&mut ids, 3, false, |id| *id), Some(2));
+        assert_eq!(ids, [2, 3, 4]);
+    }
+
+    #[test]
+    fn newer_pages_drop_the_newest_extra_row() {
+        let mut ids = vec![5, 6, 7, 8];
+        assert_eq!(trim_page(&mut ids, 3, true, |id| *id), Some(7));
+        assert_eq!(ids, [5, 6, 7]);
+    }
+
+    #[test]
+    fn emoji_is_trimmed() {
+        assert_eq!(validate_emoji(" 👍 ".to_string()).unwrap(), "👍");
+    }
+
+    #[test]
+    fn plain_ascii_is_not_an_emoji() {
+        for text in ["+1", "a", ":)", "ok"] {
+            assert!(validate_emoji(text.to_string()).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn empty_emoji_is_rejected() {
+        assert!(validate_emoji(String::new()).is_err());
+        assert!(validate_emoji("   ".to_string()).is_err());
+    }
+
+    #[test]
+    fn multi_codepoint_sequences_are_one_emoji() {
+        for emoji in ["👨‍👩‍👧‍👦", "🏳️‍🌈", "👍🏽", "🇹🇭"] {
+            assert_eq!(validate_emoji(emoji.to_string()).unwrap(), emoji);
+        }
+    }
+
+    #[test]
+    fn over_long_emoji_is_rejected() {
+        let emoji = "👍".repeat(MAX_EMOJI_LENGTH / "👍".len() + 1);
+        assert!(validate_emoji(emoji).is_err());
+        assert!(validate_emoji("👍 👍".to_string()).is_err());
+    }
+
+    #[test]
+    fn mentions_anywhere_in_the_text() {
+        assert_eq!(
+            parse_mentions("@alice meet (@bob_2) and @carol-x"),
+            ["alice", "bob_2", "carol-x"]
+        );
+    }
+
+    #[test]
+    fn email_addresses_are_not_mentions() {
+        assert!(parse_mentions("mail bob@example.com or a.b@c.io").is_empty());
+        assert_eq!(parse_mentions("ask @alice at alice@example.com"), ["alice"]);
+    }
+
+    #[test]
+    fn trailing_punctuation_is_not_part_of_the_name() {
+        assert_eq!(
+            parse_mentions("thanks @alice. @bob, @carol! @dave? @j.doe..."),
+            ["alice", "bob", "carol", "dave", "j.doe"]
+        );
+    }
+
+    #[test]
+    fn repeated_names_are_mentioned_once() {
+        assert_eq!(
+            parse_mentions("@alice @bob @Alice @ALICE @bob"),
+            ["alice", "bob"]
+        );
+    }
+
+    #[test]
+    fn bare_at_sign_is_ignored() {
+        assert!(parse_mentions("@").is_empty());
+        assert!(parse_mentions("see you @").is_empty());
+        assert!(parse_mentions("@@ @. @ @!").is_empty());
+    }
+
+    #[test]
+    fn mentions_are_capped() {
+        let content = (0..MAX_MENTIONS + 5)
+            .map(|i| format!("@brawler{}", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(parse_mentions(&content).len(), MAX_MENTIONS);
     }
 }
@@ -1,52 +1,69 @@
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use std::sync::Arc;
 
 use crate::domain::{
     entities::crew_memberships::CrewMemberShips,
     repositories::{
         crew_operation::CrewOperationRepository, mission_management::MissionManagementRepository,
+        notifications::NotificationRepository,
     },
     value_objects::{
         base64_image::Base64Image,
-        mission_model::{AddMissionModel, EditMissionModel},
+        mission_model::{AddMissionModel, DeletedMissionModel, EditMissionModel},
+        mission_statuses::MissionStatuses,
         realtime::RealtimeEvent,
         uploaded_image::UploadedImage,
     },
 };
 use crate::infrastructure::{cloudinary::UploadImageOptions, realtime::SharedRealtimeHub};
 
-pub struct MissionManagementUseCase<T1, T3>
+// A deleted mission can be restored for this long, after that it is purged
+pub const MISSION_RESTORE_GRACE_DAYS: i64 = 30;
+
+pub struct MissionManagementUseCase<T1, T3, T4>
 where
     T1: MissionManagementRepository + Send + Sync,
     T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     mission_management_repository: Arc<T1>,
     crew_operation_repository: Arc<T3>,
+    notification_repository: Arc<T4>,
     pub realtime_hub: SharedRealtimeHub,
 }
 
-use anyhow::Result;
-impl<T1, T3> MissionManagementUseCase<T1, T3>
+use crate::application::{
+    errors::{AppError, AppResult},
+    use_cases::notifications::notify,
+};
+impl<T1, T3, T4> MissionManagementUseCase<T1, T3, T4>
 where
     T1: MissionManagementRepository + Send + Sync,
     T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     pub fn new(
         mission_management_repository: Arc<T1>,
         crew_operation_repository: Arc<T3>,
+        notification_repository: Arc<T4>,
         realtime_hub: SharedRealtimeHub,
     ) -> Self {
         Self {
             mission_management_repository,
             crew_operation_repository,
+            notification_repository,
             realtime_hub,
         }
     }
 
-    pub async fn add(&self, chief_id: i32, mut add_mission_model: AddMissionModel) -> Result<i32> {
+    pub async fn add(
+        &self,
+        chief_id: i32,
+        mut add_mission_model: AddMissionModel,
+    ) -> AppResult<i32> {
         if add_mission_model.name.trim().is_empty() || add_mission_model.name.trim().len() < 3 {
-            return Err(anyhow::anyhow!(
-                "Mission name must be at least 3 characters long."
+            return Err(AppError::Validation(
+                "Mission name must be at least 3 characters long.".to_string(),
             ));
         }
 
@@ -58,6 +75,11 @@ where
             }
         });
 
+        validate_schedule(
+            add_mission_model.scheduled_start_at,
+            add_mission_model.deadline_at,
+        )?;
+
         let code = self.generate_random_code();
         let insert_mission_entity = add_mission_model.to_entity_with_code(chief_id, code);
 
@@ -87,13 +109,13 @@ where
         mission_id: i32,
         chief_id: i32,
         mut edit_mission_model: EditMissionModel,
-    ) -> Result<i32> {
+    ) -> AppResult<i32> {
         if let Some(mission_name) = &edit_mission_model.name {
             if mission_name.trim().is_empty() {
                 edit_mission_model.name = None;
             } else if mission_name.trim().len() < 3 {
-                return Err(anyhow::anyhow!(
-                    "Mission name must be at least 3 characters long."
+                return Err(AppError::Validation(
+                    "Mission name must be at least 3 characters long.".to_string(),
                 ));
             } else {
                 edit_mission_model.name = Some(mission_name.trim().to_string());
@@ -108,69 +130,188 @@ where
             }
         });
 
+        let mission = self
+            .mission_management_repository
+            .find(mission_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Mission not found".to_string()))?;
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the Chief can edit the mission".to_string(),
+            ));
+        }
+
+        validate_schedule(
+            edit_mission_model.scheduled_start_at,
+            edit_mission_model.deadline_at,
+        )?;
+        // Half of the schedule may be left as it is stored
+        validate_schedule_order(
+            edit_mission_model
+                .scheduled_start_at
+                .or(mission.scheduled_start_at.map(|start| start.and_utc())),
+            edit_mission_model
+                .deadline_at
+                .or(mission.deadline_at.map(|deadline| deadline.and_utc())),
+        )?;
+
         let edit_mission_entity = edit_mission_model.to_entity(chief_id);
 
+        // None when the mission was deleted in the meantime
         let result = self
             .mission_management_repository
             .edit(mission_id, edit_mission_entity)
-            .await?;
+            .await?
+            .ok_or_else(|| AppError::NotFound("Mission not found".to_string()))?;
 
         // Logic to kick out excess participants if max_participants is reduced
-        if let Some(new_max) = edit_mission_model.max_participants {
-            if new_max > 0 {
-                let members = self
-                    .crew_operation_repository
-                    .get_members_ordered_by_joined_at(mission_id)
-                    .await?;
-
-                if members.len() > new_max as usize {
-                    let to_kick = &members[new_max as usize..];
-                    for &brawler_id in to_kick {
-                        // Don't kick the chief (though chief should be the first one usually)
-                        if brawler_id == chief_id {
-                            continue;
-                        }
-
-                        self.crew_operation_repository
-                            .leave(CrewMemberShips {
-                                mission_id,
-                                brawler_id,
-                            })
-                            .await?;
-
-                        self.realtime_hub.broadcast(RealtimeEvent::MissionLeft {
+        if let Some(new_max) = edit_mission_model.max_participants
+            && new_max > 0
+        {
+            let members = self
+                .crew_operation_repository
+                .get_members_ordered_by_joined_at(mission_id)
+                .await?;
+
+            if members.len() > new_max as usize {
+                let to_kick = &members[new_max as usize..];
+                for &brawler_id in to_kick {
+                    // Don't kick the chief (though chief should be the first one usually)
+                    if brawler_id == chief_id {
+                        continue;
+                    }
+
+                    self.crew_operation_repository
+                        .leave(CrewMemberShips {
                             mission_id,
                             brawler_id,
-                        });
-                    }
+                        })
+                        .await?;
+
+                    let event = RealtimeEvent::MissionLeft {
+                        mission_id,
+                        brawler_id,
+                    };
+                    notify(self.notification_repository.as_ref(), &[brawler_id], &event).await;
+                    self.realtime_hub.broadcast(event);
                 }
             }
         }
 
-        self.realtime_hub.broadcast(RealtimeEvent::MissionUpdated {
+        let event = RealtimeEvent::MissionUpdated {
             mission_id,
             chief_id,
-        });
+        };
+        match self.crew_ids_except_chief(mission_id, chief_id).await {
+            Ok(crew_ids) => notify(self.notification_repository.as_ref(), &crew_ids, &event).await,
+            Err(e) => tracing::error!("Failed to load crew for notifications: {:?}", e),
+        }
+        self.realtime_hub.broadcast(event);
 
         Ok(result)
     }
 
-    pub async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<()> {
-        // Broadcast to all members that the mission is being deleted
-        self.realtime_hub
-            .broadcast(RealtimeEvent::MissionDeleted { mission_id });
+    pub async fn remove(&self, mission_id: i32, chief_id: i32) -> AppResult<()> {
+        let mission = self
+            .mission_management_repository
+            .find(mission_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Mission not found".to_string()))?;
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the Chief can delete the mission".to_string(),
+            ));
+        }
+
+        // Anyone else's membership is history worth keeping, so those missions are cancelled instead
+        let crew_ids = self.crew_ids_except_chief(mission_id, chief_id).await?;
+        if !crew_ids.is_empty() {
+            return Err(AppError::Conflict(
+                "Mission still has crew members, cancel it instead".to_string(),
+            ));
+        }
 
-        self.mission_management_repository
+        // False when the mission was deleted or someone joined in the meantime
+        let removed = self
+            .mission_management_repository
             .remove(mission_id, chief_id)
             .await?;
+        if !removed {
+            return Err(AppError::Conflict(
+                "Mission changed in the meantime".to_string(),
+            ));
+        }
+
+        self.realtime_hub
+            .broadcast(RealtimeEvent::MissionDeleted { mission_id });
         Ok(())
     }
 
+    pub async fn get_deleted(&self, chief_id: i32) -> AppResult<Vec<DeletedMissionModel>> {
+        let grace = Duration::days(MISSION_RESTORE_GRACE_DAYS);
+        let since = Utc::now().naive_utc() - grace;
+
+        let missions = self
+            .mission_management_repository
+            .get_deleted(chief_id, since)
+            .await?;
+
+        Ok(missions
+            .iter()
+            .map(|mission| {
+                let deleted_at = mission.deleted_at.unwrap_or(mission.updated_at);
+                mission.to_deleted_model(deleted_at + grace)
+            })
+            .collect())
+    }
+
+    pub async fn restore(&self, mission_id: i32, chief_id: i32) -> AppResult<i32> {
+        let since = Utc::now().naive_utc() - Duration::days(MISSION_RESTORE_GRACE_DAYS);
+
+        let mission = self
+            .mission_management_repository
+            .get_deleted(chief_id, since)
+            .await?
+            .into_iter()
+            .find(|mission| mission.id == mission_id)
+            .ok_or_else(|| {
+                AppError::NotFound("Mission not found or can no longer be restored".to_string())
+            })?;
+
+        // An active mission coming back must not give the chief a second one
+        let status: MissionStatuses = mission.status.parse()?;
+        if status.is_active()
+            && self
+                .crew_operation_repository
+                .get_current_mission(chief_id)
+                .await?
+                .is_some()
+        {
+            return Err(AppError::Conflict(
+                "You are already in an active mission. Leave or finish it before restoring this one."
+                    .to_string(),
+            ));
+        }
+
+        let mission_id = self
+            .mission_management_repository
+            .restore(mission_id, chief_id, since)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Mission can no longer be restored".to_string()))?;
+
+        self.realtime_hub.broadcast(RealtimeEvent::MissionRestored {
+            mission_id,
+            chief_id,
+        });
+
+        Ok(mission_id)
+    }
+
     pub async fn upload_image(
         &self,
         base64_image: String,
         brawler_id: i32,
-    ) -> Result<UploadedImage> {
+    ) -> AppResult<UploadedImage> {
         let option = UploadImageOptions {
             folder: Some("missions".to_string()),
             public_id: Some(format!("mission_{}_{}", brawler_id, Utc::now().timestamp())),
@@ -185,6 +326,18 @@ where
         Ok(uploaded_image)
     }
 
+    async fn crew_ids_except_chief(&self, mission_id: i32, chief_id: i32) -> AppResult<Vec<i32>> {
+        let members = self
+            .crew_operation_repository
+            .get_members_ordered_by_joined_at(mission_id)
+            .await?;
+
+        Ok(members
+            .into_iter()
+            .filter(|brawler_id| *brawler_id != chief_id)
+            .collect())
+    }
+
     fn generate_random_code(&self) -> String {
         use uuid::Uuid;
         Uuid::new_v4()
@@ -196,3 +349,38 @@ where
             .to_uppercase()
     }
 }
+
+fn validate_schedule(
+    scheduled_start_at: Option<DateTime<Utc>>,
+    deadline_at: Option<DateTime<Utc>>,
+) -> AppResult<()> {
+    let now = Utc::now();
+
+    if scheduled_start_at.is_some_and(|start| start <= now) {
+        return Err(AppError::Validation(
+            "Scheduled start must be in the future".to_string(),
+        ));
+    }
+    if deadline_at.is_some_and(|deadline| deadline <= now) {
+        return Err(AppError::Validation(
+            "Deadline must be in the future".to_string(),
+        ));
+    }
+
+    validate_schedule_order(scheduled_start_at, deadline_at)
+}
+
+fn validate_schedule_order(
+    scheduled_start_at: Option<DateTime<Utc>>,
+    deadline_at: Option<DateTime<Utc>>,
+) -> AppResult<()> {
+    if let (Some(start), Some(deadline)) = (scheduled_start_at, deadline_at)
+        && deadline <= start
+    {
+        return Err(AppError::Validation(
+            "Deadline must be after the scheduled start".to_string(),
+        ));
+    }
+
+    Ok(())
+}
@@ -1,155 +1,221 @@
 use std::sync::Arc;
 
-use anyhow::Result;
+use crate::application::{
+    errors::{AppError, AppResult},
+    use_cases::notifications::notify,
+};
 
 use crate::domain::{
     repositories::{
         mission_operation::MissionOperationRepository, mission_viewing::MissionViewingRepository,
+        notifications::NotificationRepository,
     },
     value_objects::{mission_statuses::MissionStatuses, realtime::RealtimeEvent},
 };
 use crate::infrastructure::realtime::SharedRealtimeHub;
-pub struct MissionOperationUseCase<T1, T2>
+
+const MAX_CANCEL_REASON_CHARS: usize = 500;
+
+pub struct MissionOperationUseCase<T1, T2, T3>
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     mission_operation_repository: Arc<T1>,
     mission_viewing_repository: Arc<T2>,
+    notification_repository: Arc<T3>,
     pub realtime_hub: SharedRealtimeHub,
 }
 
-impl<T1, T2> MissionOperationUseCase<T1, T2>
+impl<T1, T2, T3> MissionOperationUseCase<T1, T2, T3>
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     pub fn new(
         mission_operation_repository: Arc<T1>,
         mission_viewing_repository: Arc<T2>,
+        notification_repository: Arc<T3>,
         realtime_hub: SharedRealtimeHub,
     ) -> Self {
         Self {
             mission_operation_repository,
             mission_viewing_repository,
+            notification_repository,
             realtime_hub,
         }
     }
 
-    pub async fn in_progress(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
+    pub async fn in_progress(&self, mission_id: i32, chief_id: i32) -> AppResult<i32> {
         let mission = self.mission_viewing_repository.get_one(mission_id).await?;
+        let status: MissionStatuses = mission.status.parse()?;
 
         let crew_count = self
             .mission_viewing_repository
             .crew_counting(mission_id)
             .await?;
 
-        let is_status_open = mission.status == MissionStatuses::Open.to_string();
+        if !status.can_transition_to(MissionStatuses::InProgress) {
+            return Err(AppError::Conflict(format!(
+                "Mission status must be Open to start. Current: {}",
+                mission.status
+            )));
+        }
+        if crew_count <= 0 {
+            return Err(AppError::Conflict(
+                "Mission must have at least one crew member".to_string(),
+            ));
+        }
+        if mission.max_participants > 0 && crew_count > mission.max_participants as i64 {
+            return Err(AppError::Conflict(format!(
+                "Cannot start: Crew members ({}) exceed the limit ({})",
+                crew_count, mission.max_participants
+            )));
+        }
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the Chief can start the mission".to_string(),
+            ));
+        }
 
-        let is_over_limit =
-            mission.max_participants > 0 && crew_count > mission.max_participants as i64;
+        self.change_status(mission_id, status, MissionStatuses::InProgress, chief_id)
+            .await
+    }
 
-        let update_condition =
-            is_status_open && crew_count > 0 && mission.chief_id == chief_id && !is_over_limit;
-        if !update_condition {
-            if !is_status_open {
-                return Err(anyhow::anyhow!(
-                    "Mission status must be Open to start. Current: {}",
-                    mission.status
-                ));
-            }
-            if crew_count <= 0 {
-                return Err(anyhow::anyhow!(
-                    "Mission must have at least one crew member"
-                ));
-            }
-            if is_over_limit {
-                return Err(anyhow::anyhow!(
-                    "Cannot start: Crew members ({}) exceed the limit ({})",
-                    crew_count,
-                    mission.max_participants
-                ));
-            }
-            if mission.chief_id != chief_id {
-                return Err(anyhow::anyhow!("Only the Chief can start the mission"));
-            }
-            return Err(anyhow::anyhow!("Invalid condition to change stages!"));
+    pub async fn to_completed(&self, mission_id: i32, chief_id: i32) -> AppResult<i32> {
+        let mission = self.mission_viewing_repository.get_one(mission_id).await?;
+        let status: MissionStatuses = mission.status.parse()?;
+
+        if !status.can_transition_to(MissionStatuses::Completed) {
+            return Err(AppError::Conflict(format!(
+                "Mission must be In Progress to complete. Current: {}",
+                mission.status
+            )));
+        }
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the Chief can complete the mission".to_string(),
+            ));
         }
 
-        let result = self
-            .mission_operation_repository
-            .to_progress(mission_id, chief_id)
-            .await?;
+        self.change_status(mission_id, status, MissionStatuses::Completed, chief_id)
+            .await
+    }
 
-        self.realtime_hub
-            .broadcast(RealtimeEvent::MissionStatusChanged {
-                mission_id,
-                status: MissionStatuses::InProgress.to_string(),
-                brawler_id: chief_id,
-            });
+    pub async fn to_failed(&self, mission_id: i32, chief_id: i32) -> AppResult<i32> {
+        let mission = self.mission_viewing_repository.get_one(mission_id).await?;
+        let status: MissionStatuses = mission.status.parse()?;
 
-        Ok(result)
+        if !status.can_transition_to(MissionStatuses::Failed) {
+            return Err(AppError::Conflict(format!(
+                "Mission must be In Progress to fail. Current: {}",
+                mission.status
+            )));
+        }
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the Chief can fail the mission".to_string(),
+            ));
+        }
+
+        self.change_status(mission_id, status, MissionStatuses::Failed, chief_id)
+            .await
     }
-    pub async fn to_completed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
+
+    pub async fn to_cancelled(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+        reason: String,
+    ) -> AppResult<i32> {
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(AppError::Validation(
+                "A reason is required to cancel the mission".to_string(),
+            ));
+        }
+        if reason.chars().count() > MAX_CANCEL_REASON_CHARS {
+            return Err(AppError::Validation(format!(
+                "Reason must be at most {} characters",
+                MAX_CANCEL_REASON_CHARS
+            )));
+        }
+
         let mission = self.mission_viewing_repository.get_one(mission_id).await?;
+        let status: MissionStatuses = mission.status.parse()?;
 
-        let update_condition = mission.status == MissionStatuses::InProgress.to_string()
-            && mission.chief_id == chief_id;
-        if !update_condition {
-            if mission.status != MissionStatuses::InProgress.to_string() {
-                return Err(anyhow::anyhow!(
-                    "Mission must be In Progress to complete. Current: {}",
-                    mission.status
-                ));
-            }
-            if mission.chief_id != chief_id {
-                return Err(anyhow::anyhow!("Only the Chief can complete the mission"));
-            }
-            return Err(anyhow::anyhow!("Invalid condition to change stages!"));
+        if !status.can_transition_to(MissionStatuses::Cancelled) {
+            return Err(AppError::Conflict(format!(
+                "Mission must be Open or In Progress to cancel. Current: {}",
+                mission.status
+            )));
+        }
+        if mission.chief_id != chief_id {
+            return Err(AppError::Forbidden(
+                "Only the Chief can cancel the mission".to_string(),
+            ));
         }
+
         let result = self
             .mission_operation_repository
-            .to_completed(mission_id, chief_id)
-            .await?;
+            .cancel(mission_id, status, reason, chief_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("Mission status changed in the meantime".to_string())
+            })?;
 
-        self.realtime_hub
-            .broadcast(RealtimeEvent::MissionStatusChanged {
-                mission_id,
-                status: MissionStatuses::Completed.to_string(),
-                brawler_id: chief_id,
-            });
+        self.notify_status_changed(mission_id, chief_id, MissionStatuses::Cancelled)
+            .await;
 
         Ok(result)
     }
-    pub async fn to_failed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let mission = self.mission_viewing_repository.get_one(mission_id).await?;
 
-        let update_condition = mission.status == MissionStatuses::InProgress.to_string()
-            && mission.chief_id == chief_id;
-        if !update_condition {
-            if mission.status != MissionStatuses::InProgress.to_string() {
-                return Err(anyhow::anyhow!(
-                    "Mission must be In Progress to fail. Current: {}",
-                    mission.status
-                ));
-            }
-            if mission.chief_id != chief_id {
-                return Err(anyhow::anyhow!("Only the Chief can fail the mission"));
-            }
-            return Err(anyhow::anyhow!("Invalid condition to change stages!"));
-        }
+    async fn change_status(
+        &self,
+        mission_id: i32,
+        from: MissionStatuses,
+        to: MissionStatuses,
+        chief_id: i32,
+    ) -> AppResult<i32> {
         let result = self
             .mission_operation_repository
-            .to_failed(mission_id, chief_id)
-            .await?;
+            .change_status(mission_id, from, to, Some(chief_id))
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("Mission status changed in the meantime".to_string())
+            })?;
 
-        self.realtime_hub
-            .broadcast(RealtimeEvent::MissionStatusChanged {
-                mission_id,
-                status: MissionStatuses::Failed.to_string(),
-                brawler_id: chief_id,
-            });
+        self.notify_status_changed(mission_id, chief_id, to).await;
 
         Ok(result)
     }
+
+    // The crew learns about it even if they were offline; the chief made the change
+    async fn notify_status_changed(&self, mission_id: i32, chief_id: i32, status: MissionStatuses) {
+        let event = RealtimeEvent::MissionStatusChanged {
+            mission_id,
+            status: status.to_string(),
+            brawler_id: Some(chief_id),
+        };
+
+        match self
+            .mission_viewing_repository
+            .get_mission_count(mission_id)
+            .await
+        {
+            Ok(crew) => {
+                let crew_ids: Vec<i32> = crew
+                    .iter()
+                    .map(|brawler| brawler.brawler_id)
+                    .filter(|brawler_id| *brawler_id != chief_id)
+                    .collect();
+                notify(self.notification_repository.as_ref(), &crew_ids, &event).await;
+            }
+            Err(e) => tracing::error!("Failed to load crew for notifications: {:?}", e),
+        }
+
+        self.realtime_hub.broadcast(event);
+    }
 }
@@ -0,0 +1,168 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use std::sync::Arc;
+
+use crate::application::{
+    errors::AppResult,
+    use_cases::{mission_management::MISSION_RESTORE_GRACE_DAYS, notifications::notify},
+};
+use crate::domain::{
+    entities::missions::MissionEntity,
+    repositories::{
+        mission_management::MissionManagementRepository,
+        mission_operation::MissionOperationRepository, mission_viewing::MissionViewingRepository,
+        notifications::NotificationRepository,
+    },
+    value_objects::{mission_statuses::MissionStatuses, realtime::RealtimeEvent},
+};
+use crate::infrastructure::realtime::SharedRealtimeHub;
+
+// How long before the scheduled start the crew is reminded
+const START_REMINDER_LEAD_MINUTES: i64 = 15;
+
+pub struct MissionSchedulerUseCase<T1, T2, T3, T4>
+where
+    T1: MissionOperationRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionManagementRepository + Send + Sync,
+{
+    mission_operation_repository: Arc<T1>,
+    mission_viewing_repository: Arc<T2>,
+    notification_repository: Arc<T3>,
+    mission_management_repository: Arc<T4>,
+    pub realtime_hub: SharedRealtimeHub,
+}
+
+impl<T1, T2, T3, T4> MissionSchedulerUseCase<T1, T2, T3, T4>
+where
+    T1: MissionOperationRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionManagementRepository + Send + Sync,
+{
+    pub fn new(
+        mission_operation_repository: Arc<T1>,
+        mission_viewing_repository: Arc<T2>,
+        notification_repository: Arc<T3>,
+        mission_management_repository: Arc<T4>,
+        realtime_hub: SharedRealtimeHub,
+    ) -> Self {
+        Self {
+            mission_operation_repository,
+            mission_viewing_repository,
+            notification_repository,
+            mission_management_repository,
+            realtime_hub,
+        }
+    }
+
+    // Each step runs even when an earlier one failed
+    pub async fn run(&self) {
+        let now = Utc::now().naive_utc();
+
+        if let Err(e) = self.fail_overdue(now).await {
+            tracing::error!("Failed to expire overdue missions: {}", e);
+        }
+        if let Err(e) = self.remind_upcoming(now).await {
+            tracing::error!("Failed to send start reminders: {}", e);
+        }
+        if let Err(e) = self.purge_deleted(now).await {
+            tracing::error!("Failed to purge deleted missions: {}", e);
+        }
+    }
+
+    async fn fail_overdue(&self, now: NaiveDateTime) -> AppResult<()> {
+        let overdue = self.mission_operation_repository.get_overdue(now).await?;
+
+        for mission in overdue {
+            // None when the chief finished it or another instance got there first
+            let changed = match self
+                .mission_operation_repository
+                .change_status(
+                    mission.id,
+                    MissionStatuses::InProgress,
+                    MissionStatuses::Failed,
+                    None,
+                )
+                .await
+            {
+                Ok(changed) => changed,
+                Err(e) => {
+                    tracing::error!("Failed to expire mission {}: {:?}", mission.id, e);
+                    continue;
+                }
+            };
+            if changed.is_none() {
+                continue;
+            }
+
+            tracing::info!("Mission {} missed its deadline and failed", mission.id);
+
+            // Nobody on the crew made this change, so the chief is told as well
+            let event = RealtimeEvent::MissionStatusChanged {
+                mission_id: mission.id,
+                status: MissionStatuses::Failed.to_string(),
+                brawler_id: None,
+            };
+            self.notify_crew(&mission, &event).await;
+            self.realtime_hub.broadcast(event);
+        }
+
+        Ok(())
+    }
+
+    async fn remind_upcoming(&self, now: NaiveDateTime) -> AppResult<()> {
+        let until = now + Duration::minutes(START_REMINDER_LEAD_MINUTES);
+        let upcoming = self
+            .mission_operation_repository
+            .claim_start_reminders(now, until)
+            .await?;
+
+        for mission in upcoming {
+            let Some(scheduled_start_at) = mission.scheduled_start_at else {
+                continue;
+            };
+
+            let event = RealtimeEvent::MissionStartingSoon {
+                mission_id: mission.id,
+                scheduled_start_at: scheduled_start_at.and_utc(),
+            };
+            self.notify_crew(&mission, &event).await;
+            self.realtime_hub.broadcast(event);
+        }
+
+        Ok(())
+    }
+
+    async fn purge_deleted(&self, now: NaiveDateTime) -> AppResult<()> {
+        let before = now - Duration::days(MISSION_RESTORE_GRACE_DAYS);
+        let purged = self
+            .mission_management_repository
+            .purge_deleted(before)
+            .await?;
+
+        if purged > 0 {
+            tracing::info!("Purged {} deleted missions past their grace period", purged);
+        }
+
+        Ok(())
+    }
+
+    async fn notify_crew(&self, mission: &MissionEntity, event: &RealtimeEvent) {
+        match self
+            .mission_viewing_repository
+            .get_mission_count(mission.id)
+            .await
+        {
+            Ok(crew) => {
+                let mut crew_ids: Vec<i32> =
+                    crew.iter().map(|brawler| brawler.brawler_id).collect();
+                if !crew_ids.contains(&mission.chief_id) {
+                    crew_ids.push(mission.chief_id);
+                }
+                notify(self.notification_repository.as_ref(), &crew_ids, event).await;
+            }
+            Err(e) => tracing::error!("Failed to load crew for notifications: {:?}", e),
+        }
+    }
+}
@@ -1,31 +1,43 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use anyhow::Result;
+use crate::application::errors::AppResult;
 
 use crate::domain::{
-    repositories::mission_viewing::MissionViewingRepository,
+    repositories::{
+        mission_chat::MissionChatRepository, mission_viewing::MissionViewingRepository,
+    },
     value_objects::{
-        brawler_model::BrawlerModel, mission_filter::MissionFilter, mission_model::MissionModel,
+        brawler_model::BrawlerModel,
+        mission_filter::MissionFilter,
+        mission_model::{MissionModel, MissionStatusHistoryModel},
     },
 };
-pub struct MissionViewingUseCase<T>
+pub struct MissionViewingUseCase<T1, T2>
 where
-    T: MissionViewingRepository + Send + Sync,
+    T1: MissionViewingRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
 {
-    mission_viewing_repository: Arc<T>,
+    mission_viewing_repository: Arc<T1>,
+    mission_chat_repository: Arc<T2>,
 }
 
-impl<T> MissionViewingUseCase<T>
+impl<T1, T2> MissionViewingUseCase<T1, T2>
 where
-    T: MissionViewingRepository + Send + Sync,
+    T1: MissionViewingRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
 {
-    pub fn new(mission_viewing_repository: Arc<T>) -> Self {
+    pub fn new(mission_viewing_repository: Arc<T1>, mission_chat_repository: Arc<T2>) -> Self {
         Self {
             mission_viewing_repository,
+            mission_chat_repository,
         }
     }
 
-    pub async fn get_one(&self, mission_id: i32) -> Result<MissionModel> {
+    pub async fn get_one(
+        &self,
+        mission_id: i32,
+        viewer_id: Option<i32>,
+    ) -> AppResult<MissionModel> {
         let crew_count = self
             .mission_viewing_repository
             .crew_counting(mission_id)
@@ -39,12 +51,18 @@ where
             .await
             .unwrap_or_else(|_| "Unknown".to_string());
 
-        let result = model.to_model(crew_count, chief_name);
+        let mut result = model.to_model(crew_count, chief_name);
+        self.attach_unread_counts(std::slice::from_mut(&mut result), viewer_id)
+            .await?;
 
         Ok(result)
     }
 
-    pub async fn get_all(&self, filter: &MissionFilter) -> Result<Vec<MissionModel>> {
+    pub async fn get_all(
+        &self,
+        filter: &MissionFilter,
+        viewer_id: Option<i32>,
+    ) -> AppResult<Vec<MissionModel>> {
         let models = self.mission_viewing_repository.get_all(filter).await?;
 
         let mut result = Vec::new();
@@ -65,10 +83,12 @@ where
             result.push(model.to_model(crew_count, chief_name));
         }
 
+        self.attach_unread_counts(&mut result, viewer_id).await?;
+
         Ok(result)
     }
 
-    pub async fn get_mission_count(&self, mission_id: i32) -> Result<Vec<BrawlerModel>> {
+    pub async fn get_mission_count(&self, mission_id: i32) -> AppResult<Vec<BrawlerModel>> {
         let result = self
             .mission_viewing_repository
             .get_mission_count(mission_id)
@@ -76,4 +96,47 @@ where
 
         Ok(result)
     }
+
+    pub async fn get_status_history(
+        &self,
+        mission_id: i32,
+    ) -> AppResult<Vec<MissionStatusHistoryModel>> {
+        // Deleted missions have no visible history either
+        self.mission_viewing_repository.get_one(mission_id).await?;
+
+        let result = self
+            .mission_viewing_repository
+            .get_status_history(mission_id)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn attach_unread_counts(
+        &self,
+        missions: &mut [MissionModel],
+        viewer_id: Option<i32>,
+    ) -> AppResult<()> {
+        let Some(viewer_id) = viewer_id else {
+            return Ok(());
+        };
+        if missions.is_empty() {
+            return Ok(());
+        }
+
+        let mission_ids = missions.iter().map(|mission| mission.id).collect();
+        let counts: HashMap<i32, i64> = self
+            .mission_chat_repository
+            .count_unread(viewer_id, mission_ids)
+            .await?
+            .into_iter()
+            .map(|count| (count.mission_id, count.unread))
+            .collect();
+
+        for mission in missions.iter_mut() {
+            mission.unread_count = counts.get(&mission.id).copied();
+        }
+
+        Ok(())
+    }
 }
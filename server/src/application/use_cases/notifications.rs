@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use crate::application::errors::{AppError, AppResult};
+use crate::domain::{
+    entities::notifications::AddNotificationEntity,
+    repositories::notifications::NotificationRepository,
+    value_objects::{
+        notification_model::{NotificationFilter, NotificationModel, UnreadCountModel},
+        realtime::RealtimeEvent,
+    },
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+pub struct NotificationUseCase<T1>
+where
+    T1: NotificationRepository + Send + Sync,
+{
+    notification_repository: Arc<T1>,
+}
+
+impl<T1> NotificationUseCase<T1>
+where
+    T1: NotificationRepository + Send + Sync,
+{
+    pub fn new(notification_repository: Arc<T1>) -> Self {
+        Self {
+            notification_repository,
+        }
+    }
+
+    pub async fn get_notifications(
+        &self,
+        brawler_id: i32,
+        filter: NotificationFilter,
+    ) -> AppResult<Vec<NotificationModel>> {
+        let limit = filter
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let entities = self
+            .notification_repository
+            .get_by_brawler(brawler_id, &filter, limit)
+            .await?;
+
+        let notifications = entities
+            .iter()
+            .filter_map(|entity| match entity.to_model() {
+                Ok(model) => Some(model),
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable notification {}: {}", entity.id, e);
+                    None
+                }
+            })
+            .collect();
+
+        Ok(notifications)
+    }
+
+    pub async fn mark_read(&self, brawler_id: i32, notification_id: i32) -> AppResult<()> {
+        let found = self
+            .notification_repository
+            .mark_read(brawler_id, notification_id)
+            .await?;
+
+        if !found {
+            return Err(AppError::NotFound("Notification not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn mark_all_read(&self, brawler_id: i32) -> AppResult<usize> {
+        Ok(self
+            .notification_repository
+            .mark_all_read(brawler_id)
+            .await?)
+    }
+
+    pub async fn unread_count(&self, brawler_id: i32) -> AppResult<UnreadCountModel> {
+        let unread = self
+            .notification_repository
+            .count_unread(brawler_id)
+            .await?;
+
+        Ok(UnreadCountModel { unread })
+    }
+}
+
+// Stores an event in the inbox of everyone it affects, so brawlers who were
+// offline still see it. The action that caused it has already happened, so a
+// failed write is only logged.
+pub async fn notify<T>(notification_repository: &T, brawler_ids: &[i32], event: &RealtimeEvent)
+where
+    T: NotificationRepository + Send + Sync,
+{
+    if brawler_ids.is_empty() {
+        return;
+    }
+
+    let entities = match brawler_ids
+        .iter()
+        .map(|brawler_id| AddNotificationEntity::new(*brawler_id, event))
+        .collect::<anyhow::Result<Vec<_>>>()
+    {
+        Ok(entities) => entities,
+        Err(e) => {
+            tracing::error!("Failed to serialize notification: {:?}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = notification_repository.add(entities).await {
+        tracing::error!("Failed to store notifications: {:?}", e);
+    }
+}
@@ -0,0 +1,130 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use std::{collections::HashSet, sync::Arc};
+
+use crate::application::errors::AppResult;
+use crate::domain::{
+    repositories::{friendships::FriendshipRepository, presence::PresenceRepository},
+    value_objects::{
+        realtime::{Audience, RealtimeEvent},
+        social_model::PresenceModel,
+    },
+};
+use crate::infrastructure::realtime::SharedRealtimeHub;
+
+// An instance that stops heartbeating for this long is presumed dead
+const STALE_AFTER_SECONDS: i64 = 45;
+
+pub fn online_since() -> NaiveDateTime {
+    Utc::now().naive_utc() - Duration::seconds(STALE_AFTER_SECONDS)
+}
+
+pub struct PresenceUseCase<T1, T2>
+where
+    T1: PresenceRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+{
+    presence_repository: Arc<T1>,
+    friendship_repository: Arc<T2>,
+    pub realtime_hub: SharedRealtimeHub,
+}
+
+impl<T1, T2> PresenceUseCase<T1, T2>
+where
+    T1: PresenceRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+{
+    pub fn new(
+        presence_repository: Arc<T1>,
+        friendship_repository: Arc<T2>,
+        realtime_hub: SharedRealtimeHub,
+    ) -> Self {
+        Self {
+            presence_repository,
+            friendship_repository,
+            realtime_hub,
+        }
+    }
+
+    // Records which brawlers gained or lost their last connection to this
+    // instance and tells their friends when that changes whether they are
+    // online anywhere
+    pub async fn sync(&self, connected: Vec<i32>, disconnected: Vec<i32>) -> AppResult<()> {
+        let instance_id = self.realtime_hub.instance_id().to_string();
+
+        let already_online: HashSet<i32> = self
+            .presence_repository
+            .get_presence(connected.clone(), online_since())
+            .await?
+            .into_iter()
+            .filter(|presence| presence.online)
+            .map(|presence| presence.brawler_id)
+            .collect();
+
+        if !connected.is_empty() {
+            self.presence_repository
+                .connect(&instance_id, connected.clone())
+                .await?;
+        }
+        if !disconnected.is_empty() {
+            self.presence_repository
+                .disconnect(&instance_id, disconnected.clone())
+                .await?;
+        }
+        self.presence_repository.heartbeat(&instance_id).await?;
+
+        let mut gone = self.presence_repository.sweep_stale(online_since()).await?;
+        gone.extend(disconnected);
+
+        let changed: Vec<i32> = connected
+            .iter()
+            .filter(|brawler_id| !already_online.contains(brawler_id))
+            .chain(gone.iter())
+            .copied()
+            .collect();
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        let presences = self
+            .presence_repository
+            .get_presence(changed, online_since())
+            .await?;
+        let connected: HashSet<i32> = connected.into_iter().collect();
+        for presence in presences {
+            // Still connected through another instance or tab, nothing to tell
+            if presence.online != connected.contains(&presence.brawler_id) {
+                continue;
+            }
+            self.announce(presence).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn announce(&self, presence: PresenceModel) -> AppResult<()> {
+        let friend_ids: Vec<i32> = self
+            .friendship_repository
+            .get_friends(presence.brawler_id)
+            .await?
+            .into_iter()
+            .map(|friendship| {
+                if friendship.user_id == presence.brawler_id {
+                    friendship.friend_id
+                } else {
+                    friendship.user_id
+                }
+            })
+            .collect();
+
+        self.realtime_hub.publish(
+            Audience::Users(friend_ids),
+            RealtimeEvent::PresenceChanged {
+                brawler_id: presence.brawler_id,
+                online: presence.online,
+                last_seen_at: presence.last_seen_at,
+            },
+        );
+
+        Ok(())
+    }
+}
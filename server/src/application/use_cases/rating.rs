@@ -1,4 +1,4 @@
-use anyhow::{Result, anyhow};
+use crate::application::errors::{AppError, AppResult};
 use std::sync::Arc;
 
 use crate::domain::{
@@ -33,10 +33,12 @@ where
         brawler_id: i32,
         rating: i32,
         comment: Option<String>,
-    ) -> Result<i32> {
+    ) -> AppResult<i32> {
         // Validate rating range
-        if rating < 1 || rating > 5 {
-            return Err(anyhow!("Rating must be between 1 and 5"));
+        if !(1..=5).contains(&rating) {
+            return Err(AppError::Validation(
+                "Rating must be between 1 and 5".to_string(),
+            ));
         }
 
         // Check if user was/is a member of this mission
@@ -48,7 +50,9 @@ where
         // For now, allow rating if the user is currently in the mission
         // In production, you might want to check if they were ever in the mission
         if current_mission != Some(mission_id) {
-            return Err(anyhow!("You must be a member of this mission to rate it"));
+            return Err(AppError::Forbidden(
+                "You must be a member of this mission to rate it".to_string(),
+            ));
         }
 
         // Check if user already rated this mission
@@ -58,7 +62,9 @@ where
             .await?;
 
         if existing_rating.is_some() {
-            return Err(anyhow!("You have already rated this mission"));
+            return Err(AppError::Conflict(
+                "You have already rated this mission".to_string(),
+            ));
         }
 
         let add_rating = AddMissionRatingEntity {
@@ -68,18 +74,24 @@ where
             comment,
         };
 
-        self.rating_repository.add_rating(add_rating).await
+        Ok(self.rating_repository.add_rating(add_rating).await?)
     }
 
-    pub async fn get_mission_ratings(&self, mission_id: i32) -> Result<MissionRatingSummary> {
-        self.rating_repository
+    pub async fn get_mission_ratings(&self, mission_id: i32) -> AppResult<MissionRatingSummary> {
+        Ok(self
+            .rating_repository
             .get_ratings_by_mission_id(mission_id)
-            .await
+            .await?)
     }
 
-    pub async fn get_user_rating(&self, mission_id: i32, brawler_id: i32) -> Result<Option<i32>> {
-        self.rating_repository
+    pub async fn get_user_rating(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> AppResult<Option<i32>> {
+        Ok(self
+            .rating_repository
             .get_rating_by_mission_and_brawler(mission_id, brawler_id)
-            .await
+            .await?)
     }
 }
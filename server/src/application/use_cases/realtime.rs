@@ -0,0 +1,158 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        errors::{AppError, AppResult},
+        use_cases::mission_chat::MissionChatUseCase,
+    },
+    config::config_loader::get_jwt_env,
+    domain::{
+        repositories::{
+            brawlers::BrawlerRepository, crew_operation::CrewOperationRepository,
+            mission_chat::MissionChatRepository, mission_viewing::MissionViewingRepository,
+            notifications::NotificationRepository, sessions::SessionRepository,
+        },
+        value_objects::realtime::RealtimeTicketModel,
+    },
+    infrastructure::{
+        self,
+        jwt::jwt_model::RealtimeTicketClaims,
+        realtime::{RealtimeSubscription, SharedRealtimeHub},
+    },
+};
+
+const TICKET_SCOPE: &str = "realtime";
+const TICKET_LIFE_TIME_SECONDS: i64 = 30;
+
+pub struct RealtimeUseCase<T1, T2, T3, T4, T5, T6>
+where
+    T1: SessionRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
+    T4: MissionViewingRepository + Send + Sync,
+    T5: BrawlerRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+{
+    session_repository: Arc<T1>,
+    crew_repository: Arc<T3>,
+    mission_chat_use_case: Arc<MissionChatUseCase<T2, T3, T4, T5, T6>>,
+    pub realtime_hub: SharedRealtimeHub,
+}
+
+impl<T1, T2, T3, T4, T5, T6> RealtimeUseCase<T1, T2, T3, T4, T5, T6>
+where
+    T1: SessionRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
+    T4: MissionViewingRepository + Send + Sync,
+    T5: BrawlerRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+{
+    pub fn new(
+        session_repository: Arc<T1>,
+        crew_repository: Arc<T3>,
+        mission_chat_use_case: Arc<MissionChatUseCase<T2, T3, T4, T5, T6>>,
+        realtime_hub: SharedRealtimeHub,
+    ) -> Self {
+        Self {
+            session_repository,
+            crew_repository,
+            mission_chat_use_case,
+            realtime_hub,
+        }
+    }
+
+    // Browsers can't set headers on a websocket handshake, so the client trades
+    // its access token for a ticket that is useless by the time it hits a log
+    pub async fn issue_ticket(
+        &self,
+        brawler_id: i32,
+        session_id: String,
+    ) -> AppResult<RealtimeTicketModel> {
+        let jwt_env = get_jwt_env()?;
+        let now = Utc::now();
+
+        let claims = RealtimeTicketClaims {
+            sub: brawler_id,
+            exp: (now + Duration::seconds(TICKET_LIFE_TIME_SECONDS)).timestamp() as usize,
+            iat: now.timestamp() as usize,
+            session_id,
+            scope: TICKET_SCOPE.to_string(),
+        };
+
+        let ticket = infrastructure::jwt::generate_realtime_ticket(jwt_env.secret, &claims)?;
+
+        Ok(RealtimeTicketModel {
+            ticket,
+            expires_in: TICKET_LIFE_TIME_SECONDS,
+        })
+    }
+
+    pub async fn verify_ticket(&self, ticket: String) -> AppResult<i32> {
+        let jwt_env = get_jwt_env()?;
+        let claims = infrastructure::jwt::verify_realtime_ticket(jwt_env.secret, ticket)
+            .map_err(|_| AppError::Unauthorized("Invalid realtime ticket".to_string()))?;
+
+        if claims.scope != TICKET_SCOPE {
+            return Err(AppError::Unauthorized(
+                "Invalid realtime ticket".to_string(),
+            ));
+        }
+
+        let session = self
+            .session_repository
+            .find_active_by_jti(&claims.session_id)
+            .await?;
+
+        match session {
+            Some(session) if session.brawler_id == claims.sub => Ok(claims.sub),
+            _ => Err(AppError::Unauthorized(
+                "Session has been revoked".to_string(),
+            )),
+        }
+    }
+
+    pub async fn connect(&self, brawler_id: i32) -> RealtimeSubscription {
+        let subscription = self.realtime_hub.subscribe(brawler_id);
+
+        if let Ok(Some(mission_id)) = self.crew_repository.get_current_mission(brawler_id).await {
+            self.realtime_hub.join_room(mission_id, brawler_id);
+        }
+
+        subscription
+    }
+
+    pub async fn subscribe_mission(&self, mission_id: i32, brawler_id: i32) -> AppResult<()> {
+        // Already seated means membership was checked when they entered the room
+        if self.realtime_hub.is_in_room(mission_id, brawler_id) {
+            return Ok(());
+        }
+
+        self.mission_chat_use_case
+            .ensure_participant(mission_id, brawler_id)
+            .await?;
+        self.realtime_hub.join_room(mission_id, brawler_id);
+
+        Ok(())
+    }
+
+    pub async fn send_message(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        content: String,
+        image: Option<String>,
+        reply_to_id: Option<i32>,
+    ) -> AppResult<i32> {
+        self.mission_chat_use_case
+            .send_message(mission_id, brawler_id, content, image, reply_to_id)
+            .await
+    }
+
+    pub async fn typing(&self, mission_id: i32, brawler_id: i32) -> AppResult<()> {
+        self.mission_chat_use_case
+            .typing(mission_id, brawler_id)
+            .await
+    }
+}
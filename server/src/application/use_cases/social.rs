@@ -1,9 +1,14 @@
+use crate::application::{
+    errors::{AppError, AppResult},
+    use_cases::{notifications::notify, presence::online_since},
+};
 use crate::domain::{
     entities::{crew_memberships::CrewMemberShips, friendships::AddFriendshipEntity},
     repositories::{
         brawlers::BrawlerRepository, crew_operation::CrewOperationRepository,
         friendships::FriendshipRepository, mission_invitations::MissionInvitationRepository,
-        mission_viewing::MissionViewingRepository,
+        mission_viewing::MissionViewingRepository, notifications::NotificationRepository,
+        presence::PresenceRepository,
     },
     value_objects::{
         mission_statuses::MissionStatuses,
@@ -11,40 +16,48 @@ use crate::domain::{
         social_model::{FriendModel, FriendshipStatusModel, MissionInvitationModel},
     },
 };
-use crate::infrastructure::realtime::SharedRealtimeHub;
-use anyhow::{Result, anyhow};
+use crate::infrastructure::realtime::{RealtimeSubscription, SharedRealtimeHub};
 use std::sync::Arc;
 
-pub struct SocialUseCase<T1, T2, T3, T4, T5>
+pub struct SocialUseCase<T1, T2, T3, T4, T5, T6, T7>
 where
     T1: FriendshipRepository + Send + Sync,
     T2: MissionInvitationRepository + Send + Sync,
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     pub friendship_repo: Arc<T1>,
     pub invitation_repo: Arc<T2>,
     pub brawlers_repo: Arc<T3>,
     pub mission_repo: Arc<T4>,
     pub crew_repo: Arc<T5>,
+    pub notification_repo: Arc<T6>,
+    pub presence_repo: Arc<T7>,
     pub realtime_hub: SharedRealtimeHub,
 }
 
-impl<T1, T2, T3, T4, T5> SocialUseCase<T1, T2, T3, T4, T5>
+impl<T1, T2, T3, T4, T5, T6, T7> SocialUseCase<T1, T2, T3, T4, T5, T6, T7>
 where
     T1: FriendshipRepository + Send + Sync,
     T2: MissionInvitationRepository + Send + Sync,
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         friendship_repo: Arc<T1>,
         invitation_repo: Arc<T2>,
         brawlers_repo: Arc<T3>,
         mission_repo: Arc<T4>,
         crew_repo: Arc<T5>,
+        notification_repo: Arc<T6>,
+        presence_repo: Arc<T7>,
         realtime_hub: SharedRealtimeHub,
     ) -> Self {
         Self {
@@ -53,21 +66,28 @@ where
             brawlers_repo,
             mission_repo,
             crew_repo,
+            notification_repo,
+            presence_repo,
             realtime_hub,
         }
     }
 
-    pub async fn add_friend(&self, user_id: i32, friend_id: i32) -> Result<i32> {
+    pub async fn add_friend(&self, user_id: i32, friend_id: i32) -> AppResult<i32> {
         if user_id == friend_id {
-            return Err(anyhow!("You cannot add yourself as a friend"));
+            return Err(AppError::Validation(
+                "You cannot add yourself as a friend".to_string(),
+            ));
         }
 
-        if let Some(_) = self
+        if self
             .friendship_repo
             .check_friendship(user_id, friend_id)
             .await?
+            .is_some()
         {
-            return Err(anyhow!("Friendship request already exists"));
+            return Err(AppError::Conflict(
+                "Friendship request already exists".to_string(),
+            ));
         }
 
         let res = self
@@ -80,44 +100,58 @@ where
             .await?;
 
         // Emit realtime event
-        self.realtime_hub.broadcast(RealtimeEvent::FriendRequest {
+        let event = RealtimeEvent::FriendRequest {
             from_id: user_id,
             to_id: friend_id,
-        });
+        };
+        notify(self.notification_repo.as_ref(), &[friend_id], &event).await;
+        self.realtime_hub.broadcast(event);
 
         Ok(res)
     }
 
-    pub async fn accept_friend(&self, user_id: i32, friend_id: i32) -> Result<()> {
+    pub async fn accept_friend(&self, user_id: i32, friend_id: i32) -> AppResult<()> {
         self.friendship_repo.accept(friend_id, user_id).await?;
 
         // Broadcast to the person who sent the request
-        self.realtime_hub.broadcast(RealtimeEvent::FriendAccepted {
+        let event = RealtimeEvent::FriendAccepted {
             from_id: user_id,
             to_id: friend_id,
-        });
+        };
+        notify(self.notification_repo.as_ref(), &[friend_id], &event).await;
+        self.realtime_hub.broadcast(event);
 
         Ok(())
     }
 
-    pub async fn reject_friend(&self, user_id: i32, friend_id: i32) -> Result<()> {
-        self.friendship_repo.reject(friend_id, user_id).await
+    pub async fn reject_friend(&self, user_id: i32, friend_id: i32) -> AppResult<()> {
+        Ok(self.friendship_repo.reject(friend_id, user_id).await?)
     }
 
-    pub async fn remove_friend(&self, user_id: i32, friend_id: i32) -> Result<()> {
-        self.friendship_repo.remove(user_id, friend_id).await
+    pub async fn remove_friend(&self, user_id: i32, friend_id: i32) -> AppResult<()> {
+        Ok(self.friendship_repo.remove(user_id, friend_id).await?)
     }
 
-    pub async fn get_friends(&self, user_id: i32) -> Result<Vec<FriendModel>> {
+    pub async fn get_friends(&self, user_id: i32) -> AppResult<Vec<FriendModel>> {
         let friendships = self.friendship_repo.get_friends(user_id).await?;
+        let friend_ids = friendships
+            .iter()
+            .map(|f| {
+                if f.user_id == user_id {
+                    f.friend_id
+                } else {
+                    f.user_id
+                }
+            })
+            .collect::<Vec<i32>>();
+        let presences = self
+            .presence_repo
+            .get_presence(friend_ids.clone(), online_since())
+            .await?;
         let mut result = Vec::new();
 
-        for f in friendships {
-            let friend_id = if f.user_id == user_id {
-                f.friend_id
-            } else {
-                f.user_id
-            };
+        for (f, friend_id) in friendships.into_iter().zip(friend_ids) {
+            let presence = presences.iter().find(|p| p.brawler_id == friend_id);
             let brawler = self.brawlers_repo.find_by_id(friend_id).await?;
 
             let current_mission_id = self
@@ -134,12 +168,14 @@ where
                 avatar_url: brawler.avatar_url,
                 status: f.status,
                 current_mission_id,
+                online: presence.is_some_and(|p| p.online),
+                last_seen_at: presence.and_then(|p| p.last_seen_at),
             });
         }
         Ok(result)
     }
 
-    pub async fn get_pending_requests(&self, user_id: i32) -> Result<Vec<FriendModel>> {
+    pub async fn get_pending_requests(&self, user_id: i32) -> AppResult<Vec<FriendModel>> {
         let requests = self.friendship_repo.get_pending_requests(user_id).await?;
         let mut result = Vec::new();
 
@@ -159,6 +195,9 @@ where
                 avatar_url: brawler.avatar_url,
                 status: r.status,
                 current_mission_id,
+                // Presence is only shared once the request is accepted
+                online: false,
+                last_seen_at: None,
             });
         }
         Ok(result)
@@ -169,7 +208,7 @@ where
         inviter_id: i32,
         invitee_id: i32,
         mission_id: i32,
-    ) -> Result<i32> {
+    ) -> AppResult<i32> {
         // Check if they are friends
         let friendship = self
             .friendship_repo
@@ -177,26 +216,32 @@ where
             .await?;
 
         if friendship.is_none() || friendship.unwrap().status != "accepted" {
-            return Err(anyhow!("You can only invite friends to your mission"));
+            return Err(AppError::Forbidden(
+                "You can only invite friends to your mission".to_string(),
+            ));
         }
 
         // Check if mission is active
         let mission = self.mission_repo.get_one(mission_id).await?;
-        let mission_status_condition = mission.status == MissionStatuses::Open.to_string()
-            || mission.status == MissionStatuses::InProgress.to_string()
-            || mission.status == MissionStatuses::Failed.to_string();
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.accepts_crew_changes() {
+            return Err(AppError::Conflict(
+                "You can only invite members to Open or In Progress missions".to_string(),
+            ));
+        }
 
-        if !mission_status_condition {
-            return Err(anyhow!(
-                "You can only invite members to Open or In Progress missions"
+        // An invitation skips the approval, so only the chief hands those out
+        if mission.approval_required && mission.chief_id != inviter_id {
+            return Err(AppError::Forbidden(
+                "Only the chief can invite to missions that require approval".to_string(),
             ));
         }
 
         // Check if inviter has permission (is a member of the mission)
         let is_inviter_member = self.crew_repo.is_member(mission_id, inviter_id).await?;
         if !is_inviter_member {
-            return Err(anyhow!(
-                "You must be a member of the mission to invite others"
+            return Err(AppError::Forbidden(
+                "You must be a member of the mission to invite others".to_string(),
             ));
         }
 
@@ -204,17 +249,19 @@ where
         if mission.max_participants > 0 {
             let crew_count = self.mission_repo.crew_counting(mission_id).await?;
             if crew_count >= mission.max_participants as i64 {
-                return Err(anyhow!(
+                return Err(AppError::Conflict(format!(
                     "Mission is full (Max {} members). Increase the limit to invite more.",
                     mission.max_participants
-                ));
+                )));
             }
         }
 
         // Check if invitee is already in any mission
         let invitee_current_mission = self.crew_repo.get_current_mission(invitee_id).await?;
         if invitee_current_mission.is_some() {
-            return Err(anyhow!("This friend is already in an active mission!"));
+            return Err(AppError::Conflict(
+                "This friend is already in an active mission!".to_string(),
+            ));
         }
 
         // Clear any existing invitation to avoid unique constraint violation
@@ -235,17 +282,18 @@ where
             .await?;
 
         // Emit realtime event
-        self.realtime_hub
-            .broadcast(RealtimeEvent::MissionInvitation {
-                mission_id,
-                inviter_id,
-                invitee_id,
-            });
+        let event = RealtimeEvent::MissionInvitation {
+            mission_id,
+            inviter_id,
+            invitee_id,
+        };
+        notify(self.notification_repo.as_ref(), &[invitee_id], &event).await;
+        self.realtime_hub.broadcast(event);
 
         Ok(res)
     }
 
-    pub async fn get_my_invitations(&self, user_id: i32) -> Result<Vec<MissionInvitationModel>> {
+    pub async fn get_my_invitations(&self, user_id: i32) -> AppResult<Vec<MissionInvitationModel>> {
         let invitations = self
             .invitation_repo
             .get_received_invitations(user_id)
@@ -284,7 +332,7 @@ where
     pub async fn get_mission_invitations(
         &self,
         mission_id: i32,
-    ) -> Result<Vec<MissionInvitationModel>> {
+    ) -> AppResult<Vec<MissionInvitationModel>> {
         let invitations = self
             .invitation_repo
             .get_mission_invitations(mission_id)
@@ -325,39 +373,39 @@ where
         user_id: i32,
         invitation_id: i32,
         accept: bool,
-    ) -> Result<i32> {
+    ) -> AppResult<i32> {
         let invitation = self.invitation_repo.get_by_id(invitation_id).await?;
 
         if invitation.invitee_id != user_id {
-            return Err(anyhow!("This invitation is not for you"));
+            return Err(AppError::Forbidden(
+                "This invitation is not for you".to_string(),
+            ));
         }
 
         if accept {
             let mission = self.mission_repo.get_one(invitation.mission_id).await?;
 
             if mission.chief_id == user_id {
-                return Err(anyhow!(
-                    "You are the chief of this mission. You are already in the squad!"
+                return Err(AppError::Conflict(
+                    "You are the chief of this mission. You are already in the squad!".to_string(),
                 ));
             }
 
             // Check if user is already in any mission
             let current_mission = self.crew_repo.get_current_mission(user_id).await?;
             if current_mission.is_some() {
-                return Err(anyhow!(
+                return Err(AppError::Conflict(
                     "You are already in an active mission. Leave it first before joining."
+                        .to_string(),
                 ));
             }
 
-            let joinable = mission.status == MissionStatuses::Open.to_string()
-                || mission.status == MissionStatuses::InProgress.to_string()
-                || mission.status == MissionStatuses::Failed.to_string();
-
-            if !joinable {
-                return Err(anyhow!(
+            let status: MissionStatuses = mission.status.parse()?;
+            if !status.accepts_crew_changes() {
+                return Err(AppError::Conflict(format!(
                     "Mission is no longer joinable (Status: {})",
                     mission.status
-                ));
+                )));
             }
 
             // Check max participants before joining via invitation
@@ -367,10 +415,10 @@ where
                     .crew_counting(invitation.mission_id)
                     .await?;
                 if crew_count >= mission.max_participants as i64 {
-                    return Err(anyhow!(
+                    return Err(AppError::Conflict(format!(
                         "Mission is full (Max {} members)",
                         mission.max_participants
-                    ));
+                    )));
                 }
             }
 
@@ -385,12 +433,18 @@ where
             self.invitation_repo.accept(invitation_id).await?;
 
             // Broadcast to the inviter
-            self.realtime_hub
-                .broadcast(RealtimeEvent::MissionInvitationAccepted {
-                    mission_id: invitation.mission_id,
-                    user_id,
-                    inviter_id: invitation.inviter_id,
-                });
+            let event = RealtimeEvent::MissionInvitationAccepted {
+                mission_id: invitation.mission_id,
+                user_id,
+                inviter_id: invitation.inviter_id,
+            };
+            notify(
+                self.notification_repo.as_ref(),
+                &[invitation.inviter_id],
+                &event,
+            )
+            .await;
+            self.realtime_hub.broadcast(event);
 
             Ok(invitation.mission_id)
         } else {
@@ -403,7 +457,7 @@ where
         &self,
         user_id: i32,
         other_id: i32,
-    ) -> Result<FriendshipStatusModel> {
+    ) -> AppResult<FriendshipStatusModel> {
         let friendship = self
             .friendship_repo
             .check_friendship(user_id, other_id)
@@ -422,4 +476,15 @@ where
             }),
         }
     }
+
+    pub async fn connect_realtime(&self, user_id: i32) -> RealtimeSubscription {
+        let subscription = self.realtime_hub.subscribe(user_id);
+
+        // Seat the user in the room of their active mission so its chat reaches them
+        if let Ok(Some(mission_id)) = self.crew_repo.get_current_mission(user_id).await {
+            self.realtime_hub.join_room(mission_id, user_id);
+        }
+
+        subscription
+    }
 }
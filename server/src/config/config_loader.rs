@@ -1,7 +1,7 @@
 use anyhow::Result;
 
 use crate::config::{
-    config_model::{CloudinaryEnv, Database, DotEnvyConfig, JwtEnv, Server},
+    config_model::{CloudinaryEnv, Database, DotEnvyConfig, JwtEnv, Realtime, Server},
     stage::Stage,
 };
 
@@ -25,6 +25,14 @@ pub fn load() -> Result<DotEnvyConfig> {
         url: std::env::var("DATABASE_URL").unwrap_or_default().parse()?,
     };
 
+    // "postgres" fans events out to every instance; LISTEN needs a direct
+    // connection, not the transaction pooler behind DATABASE_URL
+    let realtime = Realtime {
+        backend: std::env::var("REALTIME_BACKEND").unwrap_or_else(|_| "local".to_string()),
+        database_url: std::env::var("REALTIME_DATABASE_URL")
+            .unwrap_or_else(|_| database.url.clone()),
+    };
+
     let secret = std::env::var("JWT_USER_SECRET")
         .unwrap_or_else(|_| "default_secret_for_railway".to_string())
         .parse()?;
@@ -40,6 +48,7 @@ pub fn load() -> Result<DotEnvyConfig> {
     let config = DotEnvyConfig {
         server,
         database,
+        realtime,
         secret,
         refresh_secret,
         max_crew_per_mission,
@@ -72,6 +81,23 @@ pub fn get_jwt_env() -> Result<JwtEnv> {
     })
 }
 
+pub fn get_jwt_refresh_env() -> Result<JwtEnv> {
+    dotenvy::dotenv().ok();
+
+    let secret = std::env::var("JWT_USER_REFRESH_SECRET")
+        .unwrap_or_else(|_| "default_refresh_secret".to_string())
+        .parse()?;
+
+    let life_time_days = std::env::var("JWT_REFRESH_LIFE_TIME_DAYS")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse::<i64>()?;
+
+    Ok(JwtEnv {
+        secret,
+        life_time_days,
+    })
+}
+
 pub fn get_cloundinary_env() -> Result<CloudinaryEnv> {
     dotenvy::dotenv().ok();
 
@@ -10,10 +10,17 @@ pub struct Database {
     pub url: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct Realtime {
+    pub backend: String,
+    pub database_url: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct DotEnvyConfig {
     pub server: Server,
     pub database: Database,
+    pub realtime: Realtime,
     pub secret: String,
     pub refresh_secret: String,
     pub max_crew_per_mission: u32,
@@ -0,0 +1,79 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::value_objects::direct_message_model::DirectMessageModel,
+    infrastructure::database::schema::{direct_conversations, direct_messages},
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize, Deserialize)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(table_name = direct_conversations)]
+pub struct DirectConversationEntity {
+    pub id: i32,
+    pub brawler_a_id: i32,
+    pub brawler_b_id: i32,
+    pub created_at: NaiveDateTime,
+}
+
+impl DirectConversationEntity {
+    pub fn other(&self, brawler_id: i32) -> i32 {
+        if self.brawler_a_id == brawler_id {
+            self.brawler_b_id
+        } else {
+            self.brawler_a_id
+        }
+    }
+}
+
+// Build with `between` so the pair is always stored in the same order
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = direct_conversations)]
+pub struct AddDirectConversationEntity {
+    pub brawler_a_id: i32,
+    pub brawler_b_id: i32,
+}
+
+impl AddDirectConversationEntity {
+    pub fn between(brawler_id: i32, other_id: i32) -> Self {
+        Self {
+            brawler_a_id: brawler_id.min(other_id),
+            brawler_b_id: brawler_id.max(other_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize, Deserialize)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(table_name = direct_messages)]
+pub struct DirectMessageEntity {
+    pub id: i32,
+    pub conversation_id: i32,
+    pub sender_id: i32,
+    pub content: String,
+    pub created_at: NaiveDateTime,
+    pub read_at: Option<NaiveDateTime>,
+}
+
+impl DirectMessageEntity {
+    pub fn to_model(&self) -> DirectMessageModel {
+        DirectMessageModel {
+            id: self.id,
+            conversation_id: self.conversation_id,
+            sender_id: self.sender_id,
+            content: self.content.clone(),
+            created_at: self.created_at.and_utc(),
+            read_at: self.read_at.map(|read_at| read_at.and_utc()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = direct_messages)]
+pub struct AddDirectMessageEntity {
+    pub conversation_id: i32,
+    pub sender_id: i32,
+    pub content: String,
+    pub created_at: NaiveDateTime,
+}
@@ -1,4 +1,13 @@
-use crate::infrastructure::database::schema::mission_chat_messages;
+use crate::{
+    domain::value_objects::mission_chat_model::{
+        MissionChatMessageEditModel, PinnedMessageModel, QuotedMessageModel, ReactionSummaryModel,
+        ReadPositionModel,
+    },
+    infrastructure::database::schema::{
+        mission_chat_mentions, mission_chat_message_edits, mission_chat_message_reactions,
+        mission_chat_messages, mission_chat_pins, mission_chat_reads,
+    },
+};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::{Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
@@ -13,6 +22,9 @@ pub struct MissionChatMessageEntity {
     pub content: String,
     pub created_at: NaiveDateTime,
     pub image_url: Option<String>,
+    pub edited_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub reply_to_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
@@ -23,6 +35,7 @@ pub struct NewMissionChatMessageEntity {
     pub content: String,
     pub created_at: NaiveDateTime,
     pub image_url: Option<String>,
+    pub reply_to_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,4 +47,110 @@ pub struct MissionChatMessageWithBrawler {
     pub content: String,
     pub created_at: DateTime<Utc>,
     pub image_url: Option<String>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub reply_to_id: Option<i32>,
+    // Filled in by the use case
+    pub reply_to: Option<QuotedMessageModel>,
+    pub reactions: Vec<ReactionSummaryModel>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = mission_chat_message_edits)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionChatMessageEditEntity {
+    pub id: i32,
+    pub message_id: i32,
+    pub previous_content: String,
+    pub edited_at: NaiveDateTime,
+}
+
+impl MissionChatMessageEditEntity {
+    pub fn to_model(&self) -> MissionChatMessageEditModel {
+        MissionChatMessageEditModel {
+            previous_content: self.previous_content.clone(),
+            edited_at: self.edited_at.and_utc(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_chat_message_edits)]
+pub struct NewMissionChatMessageEditEntity {
+    pub message_id: i32,
+    pub previous_content: String,
+    pub edited_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = mission_chat_message_reactions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionChatReactionEntity {
+    pub message_id: i32,
+    pub brawler_id: i32,
+    pub emoji: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_chat_message_reactions)]
+pub struct AddMissionChatReactionEntity {
+    pub message_id: i32,
+    pub brawler_id: i32,
+    pub emoji: String,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = mission_chat_reads)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionChatReadEntity {
+    pub mission_id: i32,
+    pub brawler_id: i32,
+    pub last_read_message_id: i32,
+    pub read_at: NaiveDateTime,
+}
+
+impl MissionChatReadEntity {
+    pub fn to_model(&self) -> ReadPositionModel {
+        ReadPositionModel {
+            brawler_id: self.brawler_id,
+            last_read_message_id: self.last_read_message_id,
+            read_at: self.read_at.and_utc(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_chat_mentions)]
+pub struct AddMissionChatMentionEntity {
+    pub message_id: i32,
+    pub brawler_id: i32,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = mission_chat_pins)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionChatPinEntity {
+    pub message_id: i32,
+    pub mission_id: i32,
+    pub pinned_by: i32,
+    pub pinned_at: NaiveDateTime,
+}
+
+impl MissionChatPinEntity {
+    pub fn to_model(&self, message: MissionChatMessageWithBrawler) -> PinnedMessageModel {
+        PinnedMessageModel {
+            message,
+            pinned_by: self.pinned_by,
+            pinned_at: self.pinned_at.and_utc(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_chat_pins)]
+pub struct AddMissionChatPinEntity {
+    pub message_id: i32,
+    pub mission_id: i32,
+    pub pinned_by: i32,
 }
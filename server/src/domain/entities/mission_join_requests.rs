@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::{
+    domain::value_objects::mission_join_request_model::MissionJoinRequestModel,
+    infrastructure::database::schema::mission_join_requests,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(table_name = mission_join_requests)]
+pub struct MissionJoinRequestEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub brawler_id: i32,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub decided_at: Option<NaiveDateTime>,
+}
+
+impl MissionJoinRequestEntity {
+    pub fn to_model(
+        &self,
+        brawler_name: String,
+        avatar_url: Option<String>,
+    ) -> MissionJoinRequestModel {
+        MissionJoinRequestModel {
+            request_id: self.id,
+            mission_id: self.mission_id,
+            brawler_id: self.brawler_id,
+            brawler_name,
+            avatar_url,
+            status: self.status.clone(),
+            created_at: self.created_at.and_utc(),
+            decided_at: self.decided_at.map(|decided_at| decided_at.and_utc()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_join_requests)]
+pub struct AddMissionJoinRequestEntity {
+    pub mission_id: i32,
+    pub brawler_id: i32,
+    pub status: String,
+}
@@ -2,7 +2,10 @@ use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
 use crate::{
-    domain::value_objects::mission_model::MissionModel, infrastructure::database::schema::missions,
+    domain::value_objects::mission_model::{
+        DeletedMissionModel, MissionModel, MissionStatusHistoryModel,
+    },
+    infrastructure::database::schema::{mission_status_history, missions},
 };
 
 #[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
@@ -20,6 +23,10 @@ pub struct MissionEntity {
     pub image_url: Option<String>,
     pub code: String,
     pub max_participants: i32,
+    pub scheduled_start_at: Option<NaiveDateTime>,
+    pub deadline_at: Option<NaiveDateTime>,
+    pub cancel_reason: Option<String>,
+    pub approval_required: bool,
 }
 
 impl MissionEntity {
@@ -37,6 +44,26 @@ impl MissionEntity {
             max_participants: self.max_participants,
             created_at: self.created_at.and_utc(),
             updated_at: self.updated_at.and_utc(),
+            scheduled_start_at: self.scheduled_start_at.map(|start| start.and_utc()),
+            deadline_at: self.deadline_at.map(|deadline| deadline.and_utc()),
+            cancel_reason: self.cancel_reason.clone(),
+            approval_required: self.approval_required,
+            unread_count: None,
+        }
+    }
+
+    pub fn to_deleted_model(&self, restorable_until: NaiveDateTime) -> DeletedMissionModel {
+        DeletedMissionModel {
+            id: self.id,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            status: self.status.clone(),
+            image_url: self.image_url.clone(),
+            code: self.code.clone(),
+            max_participants: self.max_participants,
+            created_at: self.created_at.and_utc(),
+            deleted_at: self.deleted_at.unwrap_or(self.updated_at).and_utc(),
+            restorable_until: restorable_until.and_utc(),
         }
     }
 }
@@ -51,6 +78,9 @@ pub struct AddMissionEntity {
     pub image_url: Option<String>,
     pub code: String,
     pub max_participants: i32,
+    pub scheduled_start_at: Option<NaiveDateTime>,
+    pub deadline_at: Option<NaiveDateTime>,
+    pub approval_required: bool,
 }
 
 #[derive(Debug, Clone, AsChangeset)]
@@ -60,4 +90,42 @@ pub struct EditMissionEntity {
     pub name: Option<String>,
     pub description: Option<String>,
     pub max_participants: Option<i32>,
+    pub scheduled_start_at: Option<NaiveDateTime>,
+    pub deadline_at: Option<NaiveDateTime>,
+    // Some(None) clears it, so a moved start gets a fresh reminder
+    pub start_reminder_sent_at: Option<Option<NaiveDateTime>>,
+    pub approval_required: Option<bool>,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(table_name = mission_status_history)]
+pub struct MissionStatusHistoryEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub changed_by: Option<i32>,
+    pub changed_at: NaiveDateTime,
+}
+
+impl MissionStatusHistoryEntity {
+    pub fn to_model(&self, changed_by_name: Option<String>) -> MissionStatusHistoryModel {
+        MissionStatusHistoryModel {
+            from_status: self.from_status.clone(),
+            to_status: self.to_status.clone(),
+            changed_by: self.changed_by,
+            changed_by_name,
+            changed_at: self.changed_at.and_utc(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_status_history)]
+pub struct AddMissionStatusHistoryEntity {
+    pub mission_id: i32,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub changed_by: Option<i32>,
 }
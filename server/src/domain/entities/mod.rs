@@ -1,7 +1,13 @@
 pub mod brawlers;
 pub mod crew_memberships;
+pub mod direct_messages;
 pub mod friendships;
 pub mod mission_chat;
 pub mod mission_invitations;
+pub mod mission_join_requests;
 pub mod missions;
+pub mod notifications;
 pub mod ratings;
+pub mod realtime_events;
+pub mod refresh_tokens;
+pub mod sessions;
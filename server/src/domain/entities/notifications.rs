@@ -0,0 +1,46 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::{
+    domain::value_objects::{notification_model::NotificationModel, realtime::RealtimeEvent},
+    infrastructure::database::schema::notifications,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(table_name = notifications)]
+pub struct NotificationEntity {
+    pub id: i32,
+    pub brawler_id: i32,
+    pub event: String,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl NotificationEntity {
+    pub fn to_model(&self) -> Result<NotificationModel> {
+        Ok(NotificationModel {
+            id: self.id,
+            event: serde_json::from_str(&self.event)?,
+            read: self.read_at.is_some(),
+            created_at: self.created_at.and_utc(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = notifications)]
+pub struct AddNotificationEntity {
+    pub brawler_id: i32,
+    pub event: String,
+}
+
+impl AddNotificationEntity {
+    pub fn new(brawler_id: i32, event: &RealtimeEvent) -> Result<Self> {
+        Ok(Self {
+            brawler_id,
+            event: serde_json::to_string(event)?,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::{
+    domain::value_objects::realtime::{Audience, RealtimeEvent},
+    infrastructure::database::schema::realtime_events,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(table_name = realtime_events)]
+pub struct RealtimeEventEntity {
+    pub id: i64,
+    pub audience: String,
+    pub payload: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl RealtimeEventEntity {
+    pub fn audience(&self) -> Result<Audience> {
+        Ok(serde_json::from_str(&self.audience)?)
+    }
+
+    pub fn event(&self) -> Result<RealtimeEvent> {
+        Ok(serde_json::from_str(&self.payload)?)
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = realtime_events)]
+pub struct AddRealtimeEventEntity {
+    pub audience: String,
+    pub payload: String,
+}
+
+impl AddRealtimeEventEntity {
+    pub fn new(audience: &Audience, event: &RealtimeEvent) -> Result<Self> {
+        Ok(Self {
+            audience: serde_json::to_string(audience)?,
+            payload: serde_json::to_string(event)?,
+        })
+    }
+}
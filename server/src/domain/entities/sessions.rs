@@ -0,0 +1,42 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::{
+    domain::value_objects::session_model::SessionModel, infrastructure::database::schema::sessions,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(table_name = sessions)]
+pub struct SessionEntity {
+    pub id: i32,
+    pub jti: String,
+    pub brawler_id: i32,
+    pub user_agent: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+impl SessionEntity {
+    pub fn to_model(&self, current_jti: &str) -> SessionModel {
+        SessionModel {
+            id: self.id,
+            user_agent: self.user_agent.clone(),
+            created_at: self.created_at.and_utc(),
+            last_used_at: self.last_used_at.and_utc(),
+            expires_at: self.expires_at.and_utc(),
+            current: self.jti == current_jti,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = sessions)]
+pub struct AddSessionEntity {
+    pub jti: String,
+    pub brawler_id: i32,
+    pub user_agent: Option<String>,
+    pub expires_at: NaiveDateTime,
+}
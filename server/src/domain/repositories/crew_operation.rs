@@ -5,6 +5,7 @@ use crate::domain::entities::crew_memberships::CrewMemberShips;
 
 #[async_trait]
 pub trait CrewOperationRepository {
+    // Also cancels the brawler's pending join requests, they can only be in one mission
     async fn join(&self, crew_member_ships: CrewMemberShips) -> Result<()>;
     async fn leave(&self, crew_member_ships: CrewMemberShips) -> Result<()>;
     async fn get_current_mission(&self, brawler_id: i32) -> Result<Option<i32>>;
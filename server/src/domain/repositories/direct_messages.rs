@@ -0,0 +1,49 @@
+use crate::domain::{
+    entities::direct_messages::{
+        AddDirectConversationEntity, AddDirectMessageEntity, DirectConversationEntity,
+        DirectMessageEntity,
+    },
+    value_objects::direct_message_model::{DirectMessageCursor, DirectUnreadCountModel},
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+#[async_trait]
+pub trait DirectMessageRepository {
+    async fn get_or_create_conversation(
+        &self,
+        conversation: AddDirectConversationEntity,
+    ) -> Result<DirectConversationEntity>;
+    async fn find_conversation(
+        &self,
+        brawler_id: i32,
+        other_id: i32,
+    ) -> Result<Option<DirectConversationEntity>>;
+    async fn get_conversations(&self, brawler_id: i32) -> Result<Vec<DirectConversationEntity>>;
+    async fn save_message(&self, message: AddDirectMessageEntity) -> Result<DirectMessageEntity>;
+    // The `limit` messages before the cursor, oldest first
+    async fn get_messages(
+        &self,
+        conversation_id: i32,
+        cursor: &DirectMessageCursor,
+        limit: i64,
+    ) -> Result<Vec<DirectMessageEntity>>;
+    // The newest message of each conversation that has any
+    async fn get_last_messages(
+        &self,
+        conversation_ids: Vec<i32>,
+    ) -> Result<Vec<DirectMessageEntity>>;
+    async fn count_unread(
+        &self,
+        brawler_id: i32,
+        conversation_ids: Vec<i32>,
+    ) -> Result<Vec<DirectUnreadCountModel>>;
+    // Marks everything the other brawler sent as read; returns how many changed
+    async fn mark_read(
+        &self,
+        conversation_id: i32,
+        reader_id: i32,
+        read_at: NaiveDateTime,
+    ) -> Result<usize>;
+}
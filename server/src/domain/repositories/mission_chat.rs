@@ -1,14 +1,83 @@
-use crate::domain::entities::mission_chat::{
-    MissionChatMessageWithBrawler, NewMissionChatMessageEntity,
+use crate::domain::{
+    entities::mission_chat::{
+        AddMissionChatMentionEntity, AddMissionChatPinEntity, AddMissionChatReactionEntity,
+        MissionChatMessageEditEntity, MissionChatMessageEntity, MissionChatMessageWithBrawler,
+        MissionChatPinEntity, MissionChatReactionEntity, MissionChatReadEntity,
+        NewMissionChatMessageEntity,
+    },
+    value_objects::mission_chat_model::{
+        MissionChatCursor, MissionChatSearchHitModel, MissionUnreadCountModel,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
 #[async_trait]
 pub trait MissionChatRepository {
     async fn save_message(&self, message: NewMissionChatMessageEntity) -> Result<i32>;
+    // The `limit` messages nearest the cursor, oldest first, optionally only
+    // the replies to one message
     async fn get_messages_by_mission(
         &self,
         mission_id: i32,
+        reply_to_id: Option<i32>,
+        cursor: &MissionChatCursor,
+        limit: i64,
     ) -> Result<Vec<MissionChatMessageWithBrawler>>;
+    async fn get_messages_by_ids(
+        &self,
+        message_ids: Vec<i32>,
+    ) -> Result<Vec<MissionChatMessageWithBrawler>>;
+    async fn get_message(&self, message_id: i32) -> Result<MissionChatMessageEntity>;
+    // Keeps the replaced content in the edit history
+    async fn edit_message(
+        &self,
+        message_id: i32,
+        content: String,
+        edited_at: NaiveDateTime,
+    ) -> Result<()>;
+    async fn delete_message(&self, message_id: i32, deleted_at: NaiveDateTime) -> Result<()>;
+    async fn get_message_edits(&self, message_id: i32)
+    -> Result<Vec<MissionChatMessageEditEntity>>;
+    // Both return false when there was nothing to change
+    async fn add_reaction(&self, reaction: AddMissionChatReactionEntity) -> Result<bool>;
+    async fn remove_reaction(
+        &self,
+        message_id: i32,
+        brawler_id: i32,
+        emoji: String,
+    ) -> Result<bool>;
+    async fn count_reactions(&self, message_id: i32, emoji: String) -> Result<i64>;
+    // Oldest first across all the given messages
+    async fn get_reactions(&self, message_ids: Vec<i32>) -> Result<Vec<MissionChatReactionEntity>>;
+    // Never moves a read position backwards; returns where it ends up
+    async fn mark_read(&self, mission_id: i32, brawler_id: i32, message_id: i32) -> Result<i32>;
+    async fn get_read_positions(&self, mission_id: i32) -> Result<Vec<MissionChatReadEntity>>;
+    // Live messages from others past the brawler's read position, only for
+    // missions the brawler takes part in
+    async fn count_unread(
+        &self,
+        brawler_id: i32,
+        mission_ids: Vec<i32>,
+    ) -> Result<Vec<MissionUnreadCountModel>>;
+    async fn add_mentions(&self, mentions: Vec<AddMissionChatMentionEntity>) -> Result<()>;
+    // Both return false when there was nothing to change. Pinning returns None
+    // instead when the mission already has `max_pins` pinned messages
+    async fn pin_message(
+        &self,
+        pin: AddMissionChatPinEntity,
+        max_pins: i64,
+    ) -> Result<Option<bool>>;
+    async fn unpin_message(&self, message_id: i32) -> Result<bool>;
+    // Most recently pinned first
+    async fn get_pins(&self, mission_id: i32) -> Result<Vec<MissionChatPinEntity>>;
+    // Ranked matches among live messages and the total number of matches
+    async fn search_messages(
+        &self,
+        mission_id: i32,
+        query: String,
+        page: i64,
+        page_size: i64,
+    ) -> Result<(Vec<MissionChatSearchHitModel>, i64)>;
 }
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::mission_join_requests::{AddMissionJoinRequestEntity, MissionJoinRequestEntity},
+    value_objects::{
+        join_request_statuses::JoinRequestStatuses,
+        mission_join_request_model::MissionJoinRequestModel,
+    },
+};
+
+#[async_trait]
+pub trait MissionJoinRequestRepository {
+    async fn create(&self, entity: AddMissionJoinRequestEntity) -> Result<i32>;
+    async fn get_by_id(&self, request_id: i32) -> Result<MissionJoinRequestEntity>;
+    async fn find_pending(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> Result<Option<MissionJoinRequestEntity>>;
+    // Oldest first, so the chief sees them in the order they were sent
+    async fn get_pending_for_mission(
+        &self,
+        mission_id: i32,
+    ) -> Result<Vec<MissionJoinRequestModel>>;
+    async fn get_pending_for_brawler(
+        &self,
+        brawler_id: i32,
+    ) -> Result<Vec<MissionJoinRequestModel>>;
+    // Marks a pending request approved and adds the brawler to the crew.
+    // false when it was no longer pending.
+    async fn approve(&self, request_id: i32) -> Result<bool>;
+    // Moves a pending request to rejected or cancelled; false when it was no longer pending
+    async fn close(&self, request_id: i32, status: JoinRequestStatuses) -> Result<bool>;
+}
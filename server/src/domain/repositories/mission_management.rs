@@ -1,11 +1,32 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
-use crate::domain::entities::missions::{AddMissionEntity, EditMissionEntity};
+use crate::domain::entities::missions::{AddMissionEntity, EditMissionEntity, MissionEntity};
 
 #[async_trait]
 pub trait MissionManagementRepository {
     async fn add(&self, add_mission_entity: AddMissionEntity) -> Result<i32>;
-    async fn edit(&self, mission_id: i32, edit_mission_entity: EditMissionEntity) -> Result<i32>;
-    async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<()>;
+    // None once the mission is deleted
+    async fn find(&self, mission_id: i32) -> Result<Option<MissionEntity>>;
+    // None when the mission is deleted or isn't the chief's
+    async fn edit(
+        &self,
+        mission_id: i32,
+        edit_mission_entity: EditMissionEntity,
+    ) -> Result<Option<i32>>;
+    // False when the mission is deleted, isn't the chief's or has crew besides the chief
+    async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<bool>;
+    // The chief's missions deleted at or after `since`, most recently deleted first
+    async fn get_deleted(&self, chief_id: i32, since: NaiveDateTime) -> Result<Vec<MissionEntity>>;
+    // None when the mission isn't the chief's, isn't deleted, was deleted before `since`,
+    // or is active while the chief already is in another active mission
+    async fn restore(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+        since: NaiveDateTime,
+    ) -> Result<Option<i32>>;
+    // Hard-deletes missions deleted before `before`, along with everything in them
+    async fn purge_deleted(&self, before: NaiveDateTime) -> Result<usize>;
 }
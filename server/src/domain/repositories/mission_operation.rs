@@ -1,9 +1,37 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::domain::{
+    entities::missions::MissionEntity, value_objects::mission_statuses::MissionStatuses,
+};
 
 #[async_trait]
 pub trait MissionOperationRepository {
-    async fn to_progress(&self, mission_id: i32, chief_id: i32) -> Result<i32>;
-    async fn to_completed(&self, mission_id: i32, chief_id: i32) -> Result<i32>;
-    async fn to_failed(&self, mission_id: i32, chief_id: i32) -> Result<i32>;
+    // Only moves the mission if it is still `from`, and records the change in
+    // its status history. None when the status changed in the meantime.
+    async fn change_status(
+        &self,
+        mission_id: i32,
+        from: MissionStatuses,
+        to: MissionStatuses,
+        changed_by: Option<i32>,
+    ) -> Result<Option<i32>>;
+    // Same as `change_status` to Cancelled, keeping the chief's reason
+    async fn cancel(
+        &self,
+        mission_id: i32,
+        from: MissionStatuses,
+        reason: String,
+        changed_by: i32,
+    ) -> Result<Option<i32>>;
+    // InProgress missions whose deadline has passed
+    async fn get_overdue(&self, now: NaiveDateTime) -> Result<Vec<MissionEntity>>;
+    // Open missions starting between `now` and `until` whose crew hasn't been
+    // reminded yet. Marks them as reminded, so each is only returned once.
+    async fn claim_start_reminders(
+        &self,
+        now: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Result<Vec<MissionEntity>>;
 }
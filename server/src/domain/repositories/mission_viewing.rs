@@ -3,7 +3,10 @@ use async_trait::async_trait;
 
 use crate::domain::{
     entities::missions::MissionEntity,
-    value_objects::{brawler_model::BrawlerModel, mission_filter::MissionFilter},
+    value_objects::{
+        brawler_model::BrawlerModel, mission_filter::MissionFilter,
+        mission_model::MissionStatusHistoryModel,
+    },
 };
 
 #[async_trait]
@@ -13,4 +16,5 @@ pub trait MissionViewingRepository {
     async fn get_all(&self, mission_filter: &MissionFilter) -> Result<Vec<MissionEntity>>;
     async fn get_mission_count(&self, mission_id: i32) -> Result<Vec<BrawlerModel>>;
     async fn get_chief_name(&self, chief_id: i32) -> Result<String>;
+    async fn get_status_history(&self, mission_id: i32) -> Result<Vec<MissionStatusHistoryModel>>;
 }
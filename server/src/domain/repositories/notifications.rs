@@ -0,0 +1,21 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::notifications::{AddNotificationEntity, NotificationEntity},
+    value_objects::notification_model::NotificationFilter,
+};
+
+#[async_trait]
+pub trait NotificationRepository {
+    async fn add(&self, entities: Vec<AddNotificationEntity>) -> Result<usize>;
+    async fn get_by_brawler(
+        &self,
+        brawler_id: i32,
+        filter: &NotificationFilter,
+        limit: i64,
+    ) -> Result<Vec<NotificationEntity>>;
+    async fn mark_read(&self, brawler_id: i32, notification_id: i32) -> Result<bool>;
+    async fn mark_all_read(&self, brawler_id: i32) -> Result<usize>;
+    async fn count_unread(&self, brawler_id: i32) -> Result<i64>;
+}
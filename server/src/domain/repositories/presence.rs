@@ -0,0 +1,18 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::domain::value_objects::social_model::PresenceModel;
+
+#[async_trait]
+pub trait PresenceRepository {
+    async fn connect(&self, instance_id: &str, brawler_ids: Vec<i32>) -> Result<()>;
+    async fn disconnect(&self, instance_id: &str, brawler_ids: Vec<i32>) -> Result<()>;
+    async fn heartbeat(&self, instance_id: &str) -> Result<()>;
+    async fn sweep_stale(&self, stale_before: NaiveDateTime) -> Result<Vec<i32>>;
+    async fn get_presence(
+        &self,
+        brawler_ids: Vec<i32>,
+        online_since: NaiveDateTime,
+    ) -> Result<Vec<PresenceModel>>;
+}
@@ -0,0 +1,13 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::entities::realtime_events::{AddRealtimeEventEntity, RealtimeEventEntity};
+
+#[async_trait]
+pub trait RealtimeEventRepository {
+    async fn append(&self, entity: AddRealtimeEventEntity) -> Result<i64>;
+    async fn get_after(&self, after_id: i64, limit: i64) -> Result<Vec<RealtimeEventEntity>>;
+    async fn oldest_id(&self) -> Result<Option<i64>>;
+    async fn latest_id(&self) -> Result<Option<i64>>;
+    async fn prune_before(&self, id: i64) -> Result<usize>;
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::entities::refresh_tokens::{AddRefreshTokenEntity, RefreshTokenEntity};
+
+#[async_trait]
+pub trait RefreshTokenRepository {
+    async fn add(&self, entity: AddRefreshTokenEntity) -> Result<i32>;
+    async fn find_by_jti(&self, jti: &str) -> Result<Option<RefreshTokenEntity>>;
+    // Returns false when the token was already used or revoked (reuse)
+    async fn mark_used(&self, jti: &str) -> Result<bool>;
+    async fn revoke_family(&self, family_id: &str) -> Result<()>;
+    async fn revoke_all_for_brawler(&self, brawler_id: i32) -> Result<()>;
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::domain::entities::sessions::{AddSessionEntity, SessionEntity};
+
+#[async_trait]
+pub trait SessionRepository {
+    async fn add(&self, entity: AddSessionEntity) -> Result<i32>;
+    async fn find_active_by_jti(&self, jti: &str) -> Result<Option<SessionEntity>>;
+    async fn get_active_by_brawler(&self, brawler_id: i32) -> Result<Vec<SessionEntity>>;
+    async fn touch(&self, jti: &str, expires_at: NaiveDateTime) -> Result<()>;
+    async fn revoke(&self, brawler_id: i32, session_id: i32) -> Result<Option<String>>;
+    async fn revoke_by_jti(&self, jti: &str) -> Result<()>;
+    async fn revoke_all(&self, brawler_id: i32) -> Result<()>;
+}
@@ -5,7 +5,7 @@ use base64::{Engine, engine::general_purpose};
 pub struct Base64Image(String);
 
 impl Base64Image {
-    pub fn new(data: &String) -> Result<Self> {
+    pub fn new(data: &str) -> Result<Self> {
         if data.is_empty() {
             return Err(anyhow::anyhow!("Base64Image is empty !!"));
         }
@@ -14,7 +14,7 @@ impl Base64Image {
         let clean_data = if let Some(idx) = data.find("base64,") {
             &data[idx + 7..]
         } else {
-            data.as_str()
+            data
         };
 
         let bytes = match general_purpose::STANDARD.decode(clean_data) {
@@ -22,12 +22,8 @@ impl Base64Image {
             Err(_) => return Err(anyhow::anyhow!("Invalid base64 image data.")),
         };
         let file_type = match infer::get(&bytes) {
-            Some(t) => {
-                if t.mime_type() == "image/png" || t.mime_type() == "image/jpeg" {
-                    t.mime_type()
-                } else {
-                    return Err(anyhow::anyhow!("Invalid base64 image data."));
-                }
+            Some(t) if t.mime_type() == "image/png" || t.mime_type() == "image/jpeg" => {
+                t.mime_type()
             }
             _ => return Err(anyhow::anyhow!("Invalid base64 image data.")),
         };
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use diesel::{
+    prelude::QueryableByName,
+    sql_types::{BigInt, Int4},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectMessageModel {
+    pub id: i32,
+    pub conversation_id: i32,
+    pub sender_id: i32,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+// Without a cursor the newest messages are returned
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct DirectMessageCursor {
+    // Id of the oldest message already loaded
+    pub before: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectMessagePageModel {
+    // Oldest first
+    pub messages: Vec<DirectMessageModel>,
+    // Pass back as `before`; None once there is nothing older
+    pub next_cursor: Option<i32>,
+    // The brawlers are no longer friends, so nothing new can be sent
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectConversationModel {
+    pub conversation_id: i32,
+    pub friend_id: i32,
+    pub display_name: String,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub last_message: Option<DirectMessageModel>,
+    pub unread_count: i64,
+    pub read_only: bool,
+}
+
+// Conversations with nothing unread are left out
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct DirectUnreadCountModel {
+    #[diesel(sql_type = Int4)]
+    pub conversation_id: i32,
+    #[diesel(sql_type = BigInt)]
+    pub unread: i64,
+}
@@ -0,0 +1,62 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum JoinRequestStatuses {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+    // Withdrawn by the brawler, or closed because the mission moved on
+    Cancelled,
+}
+
+impl Display for JoinRequestStatuses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinRequestStatuses::Pending => write!(f, "pending"),
+            JoinRequestStatuses::Approved => write!(f, "approved"),
+            JoinRequestStatuses::Rejected => write!(f, "rejected"),
+            JoinRequestStatuses::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl FromStr for JoinRequestStatuses {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(JoinRequestStatuses::Pending),
+            "approved" => Ok(JoinRequestStatuses::Approved),
+            "rejected" => Ok(JoinRequestStatuses::Rejected),
+            "cancelled" => Ok(JoinRequestStatuses::Cancelled),
+            other => Err(anyhow!("Unknown join request status: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JoinRequestStatuses;
+
+    #[test]
+    fn display_from_str_round_trip() {
+        for status in [
+            JoinRequestStatuses::Pending,
+            JoinRequestStatuses::Approved,
+            JoinRequestStatuses::Rejected,
+            JoinRequestStatuses::Cancelled,
+        ] {
+            assert_eq!(
+                status.to_string().parse::<JoinRequestStatuses>().unwrap(),
+                status
+            );
+        }
+        // Stored lowercase, matching mission_join_requests_status_check
+        assert_eq!(JoinRequestStatuses::Pending.to_string(), "pending");
+        assert!("Pending".parse::<JoinRequestStatuses>().is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use diesel::{
+    prelude::QueryableByName,
+    sql_types::{BigInt, Int4},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::mission_chat::MissionChatMessageWithBrawler,
+    value_objects::brawler_model::PaginationModel,
+};
+
+// Without a cursor the newest messages are returned
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MissionChatCursor {
+    // Id of the oldest message already loaded, to page back through history
+    pub before: Option<i32>,
+    // Id of the newest message already loaded, to catch up forwards
+    pub after: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionChatPageModel {
+    // Always oldest first, whichever way the page was fetched
+    pub messages: Vec<MissionChatMessageWithBrawler>,
+    // Pass back as the same `before` or `after`; None once there is nothing left
+    pub next_cursor: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionChatMessageEditModel {
+    pub previous_content: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReactionSummaryModel {
+    pub emoji: String,
+    pub count: i64,
+    pub reacted_by_me: bool,
+}
+
+// Enough of the parent message to render a quote above a reply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotedMessageModel {
+    pub id: i32,
+    pub brawler_id: i32,
+    pub brawler_name: String,
+    pub snippet: String,
+    pub has_image: bool,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionChatThreadModel {
+    pub parent: MissionChatMessageWithBrawler,
+    // Paged like the mission chat itself, oldest first
+    pub replies: Vec<MissionChatMessageWithBrawler>,
+    pub next_cursor: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadPositionModel {
+    pub brawler_id: i32,
+    pub last_read_message_id: i32,
+    pub read_at: DateTime<Utc>,
+}
+
+// Missions with nothing unread are left out
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct MissionUnreadCountModel {
+    #[diesel(sql_type = Int4)]
+    pub mission_id: i32,
+    #[diesel(sql_type = BigInt)]
+    pub unread: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedMessageModel {
+    pub message: MissionChatMessageWithBrawler,
+    pub pinned_by: i32,
+    pub pinned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionChatSearchHitModel {
+    pub message_id: i32,
+    pub brawler_id: i32,
+    pub brawler_name: String,
+    pub created_at: DateTime<Utc>,
+    pub reply_to_id: Option<i32>,
+    pub rank: f32,
+    // HTML-escaped excerpt with the matched words wrapped in <mark>
+    pub highlighted: String,
+}
+
+// Best matches first, newest first among equal ranks
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MissionChatSearchModel {
+    pub pagination: PaginationModel,
+    pub items: Vec<MissionChatSearchHitModel>,
+}
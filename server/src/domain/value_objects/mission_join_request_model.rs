@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionJoinRequestModel {
+    pub request_id: i32,
+    pub mission_id: i32,
+    pub brawler_id: i32,
+    pub brawler_name: String,
+    pub avatar_url: Option<String>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
@@ -20,6 +20,45 @@ pub struct MissionModel {
     pub max_participants: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub scheduled_start_at: Option<DateTime<Utc>>,
+    pub deadline_at: Option<DateTime<Utc>>,
+    pub cancel_reason: Option<String>,
+    pub approval_required: bool,
+    // Only set for a signed in brawler with unread chat in the mission
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unread_count: Option<i64>,
+}
+
+// A chief's own soft-deleted mission, purged for good after `restorable_until`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedMissionModel {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub image_url: Option<String>,
+    pub code: String,
+    pub max_participants: i32,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: DateTime<Utc>,
+    pub restorable_until: DateTime<Utc>,
+}
+
+// Oldest first, starting with the mission's creation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionStatusHistoryModel {
+    pub from_status: Option<String>,
+    pub to_status: String,
+    // None when the server changed the status on its own
+    pub changed_by: Option<i32>,
+    pub changed_by_name: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentMissionModel {
+    pub mission_id: Option<i32>,
+    pub unread_count: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -28,6 +67,14 @@ pub struct AddMissionModel {
     pub description: Option<String>,
     pub image_url: Option<String>,
     pub max_participants: i32,
+    #[serde(default)]
+    pub scheduled_start_at: Option<DateTime<Utc>>,
+    // Still InProgress by then and the mission fails
+    #[serde(default)]
+    pub deadline_at: Option<DateTime<Utc>>,
+    // Brawlers send join requests the chief has to approve
+    #[serde(default)]
+    pub approval_required: bool,
 }
 
 impl AddMissionModel {
@@ -40,6 +87,9 @@ impl AddMissionModel {
             image_url: self.image_url.clone(),
             code,
             max_participants: self.max_participants,
+            scheduled_start_at: self.scheduled_start_at.map(|start| start.naive_utc()),
+            deadline_at: self.deadline_at.map(|deadline| deadline.naive_utc()),
+            approval_required: self.approval_required,
         }
     }
 }
@@ -49,6 +99,12 @@ pub struct EditMissionModel {
     pub name: Option<String>,
     pub description: Option<String>,
     pub max_participants: Option<i32>,
+    #[serde(default)]
+    pub scheduled_start_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub deadline_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub approval_required: Option<bool>,
 }
 
 impl EditMissionModel {
@@ -58,6 +114,15 @@ impl EditMissionModel {
             description: self.description.clone(),
             chief_id,
             max_participants: self.max_participants,
+            scheduled_start_at: self.scheduled_start_at.map(|start| start.naive_utc()),
+            deadline_at: self.deadline_at.map(|deadline| deadline.naive_utc()),
+            start_reminder_sent_at: self.scheduled_start_at.map(|_| None),
+            approval_required: self.approval_required,
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CancelMissionModel {
+    pub reason: String,
+}
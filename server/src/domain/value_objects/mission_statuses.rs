@@ -1,14 +1,58 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MissionStatuses {
     #[default]
     Open,
     InProgress,
     Completed,
     Failed,
+    Cancelled,
+}
+
+impl MissionStatuses {
+    // The only moves a mission may make; everything else is rejected
+    pub fn next_statuses(&self) -> &'static [MissionStatuses] {
+        match self {
+            MissionStatuses::Open => &[MissionStatuses::InProgress, MissionStatuses::Cancelled],
+            MissionStatuses::InProgress => &[
+                MissionStatuses::Completed,
+                MissionStatuses::Failed,
+                MissionStatuses::Cancelled,
+            ],
+            MissionStatuses::Completed | MissionStatuses::Failed | MissionStatuses::Cancelled => {
+                &[]
+            }
+        }
+    }
+
+    pub fn can_transition_to(&self, next: MissionStatuses) -> bool {
+        self.next_statuses().contains(&next)
+    }
+
+    // Brawlers may still join, be invited or be kicked
+    pub fn accepts_crew_changes(&self) -> bool {
+        matches!(
+            self,
+            MissionStatuses::Open | MissionStatuses::InProgress | MissionStatuses::Failed
+        )
+    }
+
+    // A brawler can only be in one active mission at a time
+    pub fn is_active(&self) -> bool {
+        matches!(self, MissionStatuses::Open | MissionStatuses::InProgress)
+    }
+
+    // The active statuses as stored, for filtering in queries
+    pub fn active() -> Vec<String> {
+        [MissionStatuses::Open, MissionStatuses::InProgress]
+            .iter()
+            .map(|status| status.to_string())
+            .collect()
+    }
 }
 
 impl Display for MissionStatuses {
@@ -18,6 +62,103 @@ impl Display for MissionStatuses {
             MissionStatuses::InProgress => write!(f, "InProgress"),
             MissionStatuses::Completed => write!(f, "Completed"),
             MissionStatuses::Failed => write!(f, "Failed"),
+            MissionStatuses::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+impl FromStr for MissionStatuses {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Open" => Ok(MissionStatuses::Open),
+            "InProgress" => Ok(MissionStatuses::InProgress),
+            "Completed" => Ok(MissionStatuses::Completed),
+            "Failed" => Ok(MissionStatuses::Failed),
+            "Cancelled" => Ok(MissionStatuses::Cancelled),
+            other => Err(anyhow!("Unknown mission status: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MissionStatuses;
+
+    const ALL: [MissionStatuses; 5] = [
+        MissionStatuses::Open,
+        MissionStatuses::InProgress,
+        MissionStatuses::Completed,
+        MissionStatuses::Failed,
+        MissionStatuses::Cancelled,
+    ];
+
+    #[test]
+    fn allowed_transitions() {
+        assert!(MissionStatuses::Open.can_transition_to(MissionStatuses::InProgress));
+        assert!(MissionStatuses::Open.can_transition_to(MissionStatuses::Cancelled));
+        assert!(MissionStatuses::InProgress.can_transition_to(MissionStatuses::Completed));
+        assert!(MissionStatuses::InProgress.can_transition_to(MissionStatuses::Failed));
+        assert!(MissionStatuses::InProgress.can_transition_to(MissionStatuses::Cancelled));
+    }
+
+    #[test]
+    fn rejected_transitions() {
+        assert!(!MissionStatuses::Open.can_transition_to(MissionStatuses::Completed));
+        assert!(!MissionStatuses::Open.can_transition_to(MissionStatuses::Failed));
+        assert!(!MissionStatuses::InProgress.can_transition_to(MissionStatuses::Open));
+        for status in ALL {
+            assert!(!status.can_transition_to(status), "{} to itself", status);
+        }
+    }
+
+    #[test]
+    fn terminal_states_go_nowhere() {
+        for status in [
+            MissionStatuses::Completed,
+            MissionStatuses::Failed,
+            MissionStatuses::Cancelled,
+        ] {
+            assert!(status.next_statuses().is_empty(), "{} is terminal", status);
+            for next in ALL {
+                assert!(!status.can_transition_to(next));
+            }
+        }
+    }
+
+    #[test]
+    fn crew_changes() {
+        assert!(MissionStatuses::Open.accepts_crew_changes());
+        assert!(MissionStatuses::InProgress.accepts_crew_changes());
+        assert!(MissionStatuses::Failed.accepts_crew_changes());
+        assert!(!MissionStatuses::Completed.accepts_crew_changes());
+        assert!(!MissionStatuses::Cancelled.accepts_crew_changes());
+    }
+
+    #[test]
+    fn active_statuses() {
+        assert_eq!(MissionStatuses::active(), vec!["Open", "InProgress"]);
+        for status in ALL {
+            assert_eq!(
+                status.is_active(),
+                MissionStatuses::active().contains(&status.to_string()),
+                "{}",
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn display_from_str_round_trip() {
+        for status in ALL {
+            assert_eq!(
+                status.to_string().parse::<MissionStatuses>().unwrap(),
+                status
+            );
         }
+        assert_eq!(MissionStatuses::Cancelled.to_string(), "Cancelled");
+        assert!("Deleted".parse::<MissionStatuses>().is_err());
+        assert!("open".parse::<MissionStatuses>().is_err());
     }
 }
@@ -1,8 +1,14 @@
 pub mod base64_image;
 pub mod brawler_model;
+pub mod direct_message_model;
+pub mod join_request_statuses;
+pub mod mission_chat_model;
 pub mod mission_filter;
+pub mod mission_join_request_model;
 pub mod mission_model;
 pub mod mission_statuses;
+pub mod notification_model;
 pub mod realtime;
+pub mod session_model;
 pub mod social_model;
 pub mod uploaded_image;
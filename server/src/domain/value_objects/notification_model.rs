@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::realtime::RealtimeEvent;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationModel {
+    pub id: i32,
+    pub event: RealtimeEvent,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct NotificationFilter {
+    #[serde(alias = "unreadOnly")]
+    pub unread_only: Option<bool>,
+    // Id of the oldest notification already loaded, for the next page
+    pub before: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnreadCountModel {
+    pub unread: i64,
+}
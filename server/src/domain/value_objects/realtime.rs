@@ -25,7 +25,8 @@ pub enum RealtimeEvent {
     MissionStatusChanged {
         mission_id: i32,
         status: String,
-        brawler_id: i32,
+        // None when the scheduler made the change
+        brawler_id: Option<i32>,
     },
     MissionDeleted {
         mission_id: i32,
@@ -34,6 +35,10 @@ pub enum RealtimeEvent {
         mission_id: i32,
         chief_id: i32,
     },
+    MissionRestored {
+        mission_id: i32,
+        chief_id: i32,
+    },
     MissionUpdated {
         mission_id: i32,
         chief_id: i32,
@@ -46,11 +51,220 @@ pub enum RealtimeEvent {
         mission_id: i32,
         brawler_id: i32,
     },
+    MissionStartingSoon {
+        mission_id: i32,
+        scheduled_start_at: DateTime<Utc>,
+    },
+    MissionJoinRequested {
+        request_id: i32,
+        mission_id: i32,
+        brawler_id: i32,
+        chief_id: i32,
+    },
+    MissionJoinRequestCancelled {
+        request_id: i32,
+        mission_id: i32,
+        brawler_id: i32,
+        chief_id: i32,
+    },
+    // Approved or rejected by the chief
+    MissionJoinRequestDecided {
+        request_id: i32,
+        mission_id: i32,
+        brawler_id: i32,
+        status: String,
+    },
     MissionChatMessage {
         mission_id: i32,
+        // Absent from events logged before messages could be edited
+        #[serde(default)]
+        message_id: i32,
         brawler_id: i32,
         brawler_name: String,
         content: String,
         created_at: DateTime<Utc>,
+        image_url: Option<String>,
+        reply_to_id: Option<i32>,
+    },
+    MissionChatMessageEdited {
+        mission_id: i32,
+        message_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    },
+    MissionChatMessageDeleted {
+        mission_id: i32,
+        message_id: i32,
+        deleted_by: i32,
+    },
+    MissionChatReactionChanged {
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+        emoji: String,
+        added: bool,
+        count: i64,
+    },
+    MissionPinsChanged {
+        mission_id: i32,
+        message_id: i32,
+        pinned: bool,
+        brawler_id: i32,
+    },
+    MissionChatRead {
+        mission_id: i32,
+        brawler_id: i32,
+        last_read_message_id: i32,
+    },
+    Mentioned {
+        mission_id: i32,
+        message_id: i32,
+        brawler_id: i32,
+        mentioned_by: i32,
+        mentioned_by_name: String,
+        snippet: String,
     },
+    MissionTyping {
+        mission_id: i32,
+        brawler_id: i32,
+        // Never logged, so this only has to make sense to live clients
+        expires_at: DateTime<Utc>,
+    },
+    DirectMessage {
+        conversation_id: i32,
+        message_id: i32,
+        sender_id: i32,
+        sender_name: String,
+        recipient_id: i32,
+        content: String,
+        created_at: DateTime<Utc>,
+    },
+    DirectMessagesRead {
+        conversation_id: i32,
+        reader_id: i32,
+        sender_id: i32,
+        read_at: DateTime<Utc>,
+    },
+    PresenceChanged {
+        brawler_id: i32,
+        online: bool,
+        last_seen_at: Option<DateTime<Utc>>,
+    },
+    // The client fell too far behind to replay, it should refetch its state
+    ResyncRequired,
+}
+
+// Who should receive an event. Mission rooms hold the connected crew of a mission.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Audience {
+    User(i32),
+    Users(Vec<i32>),
+    Mission(i32),
+    All,
+}
+
+impl RealtimeEvent {
+    pub fn audience(&self) -> Audience {
+        match self {
+            RealtimeEvent::FriendRequest { to_id, .. } => Audience::User(*to_id),
+            RealtimeEvent::MissionInvitation { invitee_id, .. } => Audience::User(*invitee_id),
+            RealtimeEvent::FriendAccepted { to_id, .. } => Audience::User(*to_id),
+            RealtimeEvent::MissionInvitationAccepted { inviter_id, .. } => {
+                Audience::User(*inviter_id)
+            }
+            // Mission boards refresh on these, so everyone gets them
+            RealtimeEvent::MissionStatusChanged { .. }
+            | RealtimeEvent::MissionDeleted { .. }
+            | RealtimeEvent::MissionCreated { .. }
+            | RealtimeEvent::MissionRestored { .. }
+            | RealtimeEvent::MissionUpdated { .. }
+            | RealtimeEvent::MissionJoined { .. }
+            | RealtimeEvent::MissionLeft { .. } => Audience::All,
+            RealtimeEvent::MissionStartingSoon { mission_id, .. }
+            | RealtimeEvent::MissionChatMessage { mission_id, .. }
+            | RealtimeEvent::MissionChatMessageEdited { mission_id, .. }
+            | RealtimeEvent::MissionChatMessageDeleted { mission_id, .. }
+            | RealtimeEvent::MissionChatReactionChanged { mission_id, .. }
+            | RealtimeEvent::MissionChatRead { mission_id, .. }
+            | RealtimeEvent::MissionPinsChanged { mission_id, .. }
+            | RealtimeEvent::MissionTyping { mission_id, .. } => Audience::Mission(*mission_id),
+            RealtimeEvent::MissionJoinRequested { chief_id, .. }
+            | RealtimeEvent::MissionJoinRequestCancelled { chief_id, .. } => {
+                Audience::User(*chief_id)
+            }
+            RealtimeEvent::MissionJoinRequestDecided { brawler_id, .. } => {
+                Audience::User(*brawler_id)
+            }
+            // Straight to the brawler, whether or not they follow the room
+            RealtimeEvent::Mentioned { brawler_id, .. } => Audience::User(*brawler_id),
+            // Both sides, so the sender's other tabs stay in sync too
+            RealtimeEvent::DirectMessage {
+                sender_id,
+                recipient_id,
+                ..
+            } => Audience::Users(vec![*sender_id, *recipient_id]),
+            RealtimeEvent::DirectMessagesRead {
+                reader_id,
+                sender_id,
+                ..
+            } => Audience::Users(vec![*reader_id, *sender_id]),
+            // Published to the brawler's friends, see PresenceUseCase
+            RealtimeEvent::PresenceChanged { brawler_id, .. } => Audience::User(*brawler_id),
+            // Only ever sent straight to a reconnecting client
+            RealtimeEvent::ResyncRequired => Audience::All,
+        }
+    }
+
+    // Not worth replaying after a reconnect, so they skip the event log
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            RealtimeEvent::MissionTyping { .. }
+                | RealtimeEvent::MissionChatRead { .. }
+                | RealtimeEvent::DirectMessagesRead { .. }
+                | RealtimeEvent::PresenceChanged { .. }
+                | RealtimeEvent::ResyncRequired
+        )
+    }
+}
+
+// Frames a websocket client may send
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum RealtimeCommand {
+    SubscribeMission {
+        mission_id: i32,
+    },
+    UnsubscribeMission {
+        mission_id: i32,
+    },
+    SendChatMessage {
+        mission_id: i32,
+        #[serde(default)]
+        content: String,
+        // Base64 png or jpeg, uploaded before the message is stored
+        image: Option<String>,
+        reply_to_id: Option<i32>,
+    },
+    Typing {
+        mission_id: i32,
+    },
+    Ping,
+}
+
+// Direct answers to a RealtimeCommand, sent only to the issuing socket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum RealtimeReply {
+    Subscribed { mission_id: i32 },
+    Unsubscribed { mission_id: i32 },
+    ChatMessageSent { mission_id: i32, message_id: i32 },
+    Pong,
+    Error { code: String, message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealtimeTicketModel {
+    pub ticket: String,
+    pub expires_in: i64,
 }
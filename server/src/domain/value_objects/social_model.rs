@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +10,8 @@ pub struct FriendModel {
     pub avatar_url: Option<String>,
     pub status: String,
     pub current_mission_id: Option<i32>,
+    pub online: bool,
+    pub last_seen_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,3 +33,10 @@ pub struct FriendshipStatusModel {
     pub initiator_id: Option<i32>,
     pub status: String, // "none", "pending", "accepted"
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceModel {
+    pub brawler_id: i32,
+    pub online: bool,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
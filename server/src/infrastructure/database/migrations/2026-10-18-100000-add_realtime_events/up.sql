@@ -0,0 +1,6 @@
+CREATE TABLE realtime_events (
+    id BIGSERIAL PRIMARY KEY,
+    audience TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
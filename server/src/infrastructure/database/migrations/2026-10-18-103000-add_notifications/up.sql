@@ -0,0 +1,10 @@
+CREATE TABLE notifications (
+    id SERIAL PRIMARY KEY,
+    brawler_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    event TEXT NOT NULL,
+    read_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_brawler_id ON notifications(brawler_id, id DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications(brawler_id) WHERE read_at IS NULL;
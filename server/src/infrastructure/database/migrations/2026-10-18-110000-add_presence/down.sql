@@ -0,0 +1,2 @@
+DROP TABLE brawler_presence;
+ALTER TABLE brawlers DROP COLUMN last_seen_at;
@@ -0,0 +1,11 @@
+ALTER TABLE brawlers ADD COLUMN last_seen_at TIMESTAMP;
+
+-- One row per brawler per server instance holding at least one of their connections
+CREATE TABLE brawler_presence (
+    instance_id VARCHAR(64) NOT NULL,
+    brawler_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    heartbeat_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (instance_id, brawler_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_brawler_presence_brawler_id ON brawler_presence(brawler_id);
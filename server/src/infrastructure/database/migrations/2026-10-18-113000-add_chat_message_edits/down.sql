@@ -0,0 +1,3 @@
+DROP TABLE mission_chat_message_edits;
+ALTER TABLE mission_chat_messages DROP COLUMN deleted_at;
+ALTER TABLE mission_chat_messages DROP COLUMN edited_at;
@@ -0,0 +1,12 @@
+ALTER TABLE mission_chat_messages ADD COLUMN edited_at TIMESTAMP;
+ALTER TABLE mission_chat_messages ADD COLUMN deleted_at TIMESTAMP;
+
+-- Previous versions of a message, newest edit last
+CREATE TABLE mission_chat_message_edits (
+    id SERIAL PRIMARY KEY,
+    message_id INT NOT NULL REFERENCES mission_chat_messages(id) ON DELETE CASCADE,
+    previous_content TEXT NOT NULL,
+    edited_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_mission_chat_message_edits_message_id ON mission_chat_message_edits(message_id);
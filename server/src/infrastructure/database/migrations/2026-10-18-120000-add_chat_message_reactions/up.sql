@@ -0,0 +1,7 @@
+CREATE TABLE mission_chat_message_reactions (
+    message_id INT NOT NULL REFERENCES mission_chat_messages(id) ON DELETE CASCADE,
+    brawler_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    emoji VARCHAR(32) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (message_id, brawler_id, emoji)
+);
@@ -0,0 +1 @@
+ALTER TABLE mission_chat_messages DROP COLUMN reply_to_id;
@@ -0,0 +1,4 @@
+ALTER TABLE mission_chat_messages
+    ADD COLUMN reply_to_id INT REFERENCES mission_chat_messages(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_mission_chat_messages_reply_to_id ON mission_chat_messages(reply_to_id);
@@ -0,0 +1,10 @@
+-- How far each brawler has read a mission chat; only ever moves forwards
+CREATE TABLE mission_chat_reads (
+    mission_id INT NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    brawler_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    last_read_message_id INT NOT NULL,
+    read_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (mission_id, brawler_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mission_chat_reads_brawler_id ON mission_chat_reads(brawler_id);
@@ -0,0 +1,8 @@
+CREATE TABLE mission_chat_mentions (
+    message_id INT NOT NULL REFERENCES mission_chat_messages(id) ON DELETE CASCADE,
+    brawler_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (message_id, brawler_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mission_chat_mentions_brawler_id ON mission_chat_mentions(brawler_id);
@@ -0,0 +1,8 @@
+CREATE TABLE mission_chat_pins (
+    message_id INT PRIMARY KEY REFERENCES mission_chat_messages(id) ON DELETE CASCADE,
+    mission_id INT NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    pinned_by INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    pinned_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_mission_chat_pins_mission_id ON mission_chat_pins(mission_id);
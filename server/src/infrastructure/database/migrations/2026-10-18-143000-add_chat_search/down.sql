@@ -0,0 +1 @@
+ALTER TABLE mission_chat_messages DROP COLUMN search_vector;
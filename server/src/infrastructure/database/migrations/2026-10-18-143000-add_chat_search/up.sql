@@ -0,0 +1,5 @@
+-- 'simple' keeps nicknames and game terms intact instead of stemming them
+ALTER TABLE mission_chat_messages
+    ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (to_tsvector('simple', content)) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_mission_chat_messages_search_vector ON mission_chat_messages USING GIN (search_vector);
@@ -0,0 +1,2 @@
+DROP TABLE direct_messages;
+DROP TABLE direct_conversations;
@@ -0,0 +1,22 @@
+-- One conversation per pair of brawlers, lower id first
+CREATE TABLE direct_conversations (
+    id SERIAL PRIMARY KEY,
+    brawler_a_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    brawler_b_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (brawler_a_id < brawler_b_id),
+    UNIQUE (brawler_a_id, brawler_b_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_direct_conversations_brawler_b_id ON direct_conversations(brawler_b_id);
+
+CREATE TABLE direct_messages (
+    id SERIAL PRIMARY KEY,
+    conversation_id INT NOT NULL REFERENCES direct_conversations(id) ON DELETE CASCADE,
+    sender_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    read_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_direct_messages_conversation_id ON direct_messages(conversation_id, id);
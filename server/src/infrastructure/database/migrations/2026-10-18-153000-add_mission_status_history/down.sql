@@ -0,0 +1,2 @@
+ALTER TABLE missions DROP CONSTRAINT missions_status_check;
+DROP TABLE mission_status_history;
@@ -0,0 +1,19 @@
+-- Every status change of a mission; from_status is NULL for its creation
+CREATE TABLE mission_status_history (
+    id SERIAL PRIMARY KEY,
+    mission_id INT NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    from_status VARCHAR(20),
+    to_status VARCHAR(20) NOT NULL,
+    -- NULL when the server changed the status on its own
+    changed_by INT REFERENCES brawlers(id) ON DELETE SET NULL,
+    changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_mission_status_history_mission_id ON mission_status_history(mission_id);
+
+-- Existing missions all started out Open
+INSERT INTO mission_status_history (mission_id, from_status, to_status, changed_by, changed_at)
+SELECT id, NULL, 'Open', chief_id, created_at FROM missions;
+
+ALTER TABLE missions
+    ADD CONSTRAINT missions_status_check CHECK (status IN ('Open', 'InProgress', 'Completed', 'Failed'));
@@ -0,0 +1,4 @@
+ALTER TABLE missions DROP CONSTRAINT missions_schedule_check;
+ALTER TABLE missions DROP COLUMN start_reminder_sent_at;
+ALTER TABLE missions DROP COLUMN deadline_at;
+ALTER TABLE missions DROP COLUMN scheduled_start_at;
@@ -0,0 +1,10 @@
+ALTER TABLE missions ADD COLUMN scheduled_start_at TIMESTAMP;
+ALTER TABLE missions ADD COLUMN deadline_at TIMESTAMP;
+-- Set once the crew has been told the start is near, cleared when it moves
+ALTER TABLE missions ADD COLUMN start_reminder_sent_at TIMESTAMP;
+
+ALTER TABLE missions
+    ADD CONSTRAINT missions_schedule_check CHECK (deadline_at > scheduled_start_at);
+
+CREATE INDEX IF NOT EXISTS idx_missions_deadline_at ON missions(deadline_at) WHERE deadline_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_missions_scheduled_start_at ON missions(scheduled_start_at) WHERE start_reminder_sent_at IS NULL;
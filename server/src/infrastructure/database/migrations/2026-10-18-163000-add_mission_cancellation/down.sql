@@ -0,0 +1,7 @@
+UPDATE missions SET status = 'Failed' WHERE status = 'Cancelled';
+
+ALTER TABLE missions DROP CONSTRAINT missions_status_check;
+ALTER TABLE missions
+    ADD CONSTRAINT missions_status_check CHECK (status IN ('Open', 'InProgress', 'Completed', 'Failed'));
+
+ALTER TABLE missions DROP COLUMN cancel_reason;
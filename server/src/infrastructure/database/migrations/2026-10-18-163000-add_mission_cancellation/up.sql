@@ -0,0 +1,6 @@
+-- Why the chief called the mission off; only set for Cancelled missions
+ALTER TABLE missions ADD COLUMN cancel_reason TEXT;
+
+ALTER TABLE missions DROP CONSTRAINT missions_status_check;
+ALTER TABLE missions
+    ADD CONSTRAINT missions_status_check CHECK (status IN ('Open', 'InProgress', 'Completed', 'Failed', 'Cancelled'));
@@ -0,0 +1,18 @@
+-- Purging a soft-deleted mission takes everything that belongs to it along
+ALTER TABLE crew_memberships
+    DROP CONSTRAINT fk_mission,
+    ADD CONSTRAINT fk_mission FOREIGN KEY (mission_id) REFERENCES missions(id) ON DELETE CASCADE;
+
+ALTER TABLE mission_invitations
+    DROP CONSTRAINT mission_invitations_mission_id_fkey,
+    ADD CONSTRAINT mission_invitations_mission_id_fkey FOREIGN KEY (mission_id) REFERENCES missions(id) ON DELETE CASCADE;
+
+ALTER TABLE mission_chat_messages
+    DROP CONSTRAINT mission_chat_messages_mission_id_fkey,
+    ADD CONSTRAINT mission_chat_messages_mission_id_fkey FOREIGN KEY (mission_id) REFERENCES missions(id) ON DELETE CASCADE;
+
+ALTER TABLE mission_ratings
+    DROP CONSTRAINT mission_ratings_mission_id_fkey,
+    ADD CONSTRAINT mission_ratings_mission_id_fkey FOREIGN KEY (mission_id) REFERENCES missions(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_missions_deleted_at ON missions(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,2 @@
+DROP TABLE mission_join_requests;
+ALTER TABLE missions DROP COLUMN approval_required;
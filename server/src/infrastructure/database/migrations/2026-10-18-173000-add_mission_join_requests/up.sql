@@ -0,0 +1,18 @@
+-- Brawlers ask to join these and the chief decides
+ALTER TABLE missions ADD COLUMN approval_required BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE mission_join_requests (
+    id SERIAL PRIMARY KEY,
+    mission_id INT NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    brawler_id INT NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- When it was approved, rejected or cancelled
+    decided_at TIMESTAMP,
+    CONSTRAINT mission_join_requests_status_check CHECK (status IN ('pending', 'approved', 'rejected', 'cancelled'))
+);
+
+-- Only one open request per brawler and mission; decided ones are kept as history
+CREATE UNIQUE INDEX IF NOT EXISTS idx_mission_join_requests_pending
+    ON mission_join_requests(mission_id, brawler_id) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_mission_join_requests_brawler_id ON mission_join_requests(brawler_id);
@@ -10,6 +10,7 @@ use crate::{
     domain::{
         entities::brawlers::{BrawlerEntity, NewBrawlerEntity},
         repositories::brawlers::BrawlerRepository,
+        value_objects::mission_statuses::MissionStatuses,
     },
     infrastructure::database::{postgresql_connection::PgPoolSquad, schema::brawlers},
 };
@@ -194,15 +195,19 @@ impl BrawlerRepository for BrawlerPostgres {
             let mut connection = db_pool.get()?;
             use crate::infrastructure::database::schema::{crew_memberships, missions};
 
+            // A deleted mission never had a crew besides its chief
             let joined_count = crew_memberships::table
+                .inner_join(missions::table)
                 .filter(crew_memberships::brawler_id.eq(brawler_id))
+                .filter(missions::deleted_at.is_null())
                 .count()
                 .get_result::<i64>(&mut connection)?;
 
             let completed_count = crew_memberships::table
                 .inner_join(missions::table)
                 .filter(crew_memberships::brawler_id.eq(brawler_id))
-                .filter(missions::status.eq("Completed"))
+                .filter(missions::status.eq(MissionStatuses::Completed.to_string()))
+                .filter(missions::deleted_at.is_null())
                 .count()
                 .get_result::<i64>(&mut connection)?;
 
@@ -1,8 +1,8 @@
 use anyhow::{Ok, Result};
 use async_trait::async_trait;
 use diesel::{
-    BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl,
-    dsl::delete, insert_into,
+    Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, dsl::delete,
+    insert_into,
 };
 use std::sync::Arc;
 
@@ -10,9 +10,11 @@ use crate::{
     domain::{
         entities::crew_memberships::CrewMemberShips,
         repositories::crew_operation::CrewOperationRepository,
+        value_objects::mission_statuses::MissionStatuses,
     },
     infrastructure::database::{
         postgresql_connection::PgPoolSquad,
+        repositories::mission_join_requests::cancel_pending_for_brawler,
         schema::{crew_memberships, missions},
     },
 };
@@ -33,9 +35,14 @@ impl CrewOperationRepository for CrewOperationPostgres {
         let db_pool = Arc::clone(&self.db_pool);
         tokio::task::spawn_blocking(move || -> Result<()> {
             let mut conn = db_pool.get()?;
-            insert_into(crew_memberships::table)
-                .values(crew_member_ships)
-                .execute(&mut conn)?;
+            conn.transaction::<(), anyhow::Error, _>(|c| {
+                let brawler_id = crew_member_ships.brawler_id;
+                insert_into(crew_memberships::table)
+                    .values(crew_member_ships)
+                    .execute(c)?;
+                cancel_pending_for_brawler(c, brawler_id)?;
+                Ok(())
+            })?;
             Ok(())
         })
         .await??;
@@ -66,11 +73,7 @@ impl CrewOperationRepository for CrewOperationPostgres {
                 .inner_join(missions::table)
                 .filter(crew_memberships::brawler_id.eq(brawler_id))
                 .filter(missions::deleted_at.is_null())
-                .filter(
-                    missions::status
-                        .eq("Open")
-                        .or(missions::status.eq("InProgress")),
-                )
+                .filter(missions::status.eq_any(MissionStatuses::active()))
                 .select(crew_memberships::mission_id)
                 .first::<i32>(&mut conn)
                 .optional()?;
@@ -83,11 +86,7 @@ impl CrewOperationRepository for CrewOperationPostgres {
             let chief_mission_id = missions::table
                 .filter(missions::chief_id.eq(brawler_id))
                 .filter(missions::deleted_at.is_null())
-                .filter(
-                    missions::status
-                        .eq("Open")
-                        .or(missions::status.eq("InProgress")),
-                )
+                .filter(missions::status.eq_any(MissionStatuses::active()))
                 .select(missions::id)
                 .first::<i32>(&mut conn)
                 .optional()?;
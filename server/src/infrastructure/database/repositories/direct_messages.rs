@@ -0,0 +1,221 @@
+use anyhow::{Ok, Result};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::{
+    prelude::*,
+    sql_types::{Array, Int4},
+};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::direct_messages::{
+            AddDirectConversationEntity, AddDirectMessageEntity, DirectConversationEntity,
+            DirectMessageEntity,
+        },
+        repositories::direct_messages::DirectMessageRepository,
+        value_objects::direct_message_model::{DirectMessageCursor, DirectUnreadCountModel},
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{direct_conversations, direct_messages},
+    },
+};
+
+pub struct DirectMessagePostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl DirectMessagePostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl DirectMessageRepository for DirectMessagePostgres {
+    async fn get_or_create_conversation(
+        &self,
+        conversation: AddDirectConversationEntity,
+    ) -> Result<DirectConversationEntity> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<DirectConversationEntity> {
+            let mut conn = db_pool.get()?;
+            diesel::insert_into(direct_conversations::table)
+                .values(&conversation)
+                .on_conflict((
+                    direct_conversations::brawler_a_id,
+                    direct_conversations::brawler_b_id,
+                ))
+                .do_nothing()
+                .execute(&mut conn)?;
+
+            let res = direct_conversations::table
+                .filter(direct_conversations::brawler_a_id.eq(conversation.brawler_a_id))
+                .filter(direct_conversations::brawler_b_id.eq(conversation.brawler_b_id))
+                .select(DirectConversationEntity::as_select())
+                .first::<DirectConversationEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn find_conversation(
+        &self,
+        brawler_id: i32,
+        other_id: i32,
+    ) -> Result<Option<DirectConversationEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let pair = AddDirectConversationEntity::between(brawler_id, other_id);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Option<DirectConversationEntity>> {
+                let mut conn = db_pool.get()?;
+                let res = direct_conversations::table
+                    .filter(direct_conversations::brawler_a_id.eq(pair.brawler_a_id))
+                    .filter(direct_conversations::brawler_b_id.eq(pair.brawler_b_id))
+                    .select(DirectConversationEntity::as_select())
+                    .first::<DirectConversationEntity>(&mut conn)
+                    .optional()?;
+                Ok(res)
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn get_conversations(&self, brawler_id: i32) -> Result<Vec<DirectConversationEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Vec<DirectConversationEntity>> {
+                let mut conn = db_pool.get()?;
+                let res = direct_conversations::table
+                    .filter(
+                        direct_conversations::brawler_a_id
+                            .eq(brawler_id)
+                            .or(direct_conversations::brawler_b_id.eq(brawler_id)),
+                    )
+                    .order_by(direct_conversations::created_at.desc())
+                    .select(DirectConversationEntity::as_select())
+                    .load::<DirectConversationEntity>(&mut conn)?;
+                Ok(res)
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn save_message(&self, message: AddDirectMessageEntity) -> Result<DirectMessageEntity> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<DirectMessageEntity> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::insert_into(direct_messages::table)
+                .values(&message)
+                .returning(DirectMessageEntity::as_returning())
+                .get_result::<DirectMessageEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_messages(
+        &self,
+        conversation_id: i32,
+        cursor: &DirectMessageCursor,
+        limit: i64,
+    ) -> Result<Vec<DirectMessageEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let before = cursor.before;
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<DirectMessageEntity>> {
+            let mut conn = db_pool.get()?;
+            let mut query = direct_messages::table
+                .filter(direct_messages::conversation_id.eq(conversation_id))
+                .into_boxed();
+
+            if let Some(before) = before {
+                query = query.filter(direct_messages::id.lt(before));
+            }
+
+            let mut res = query
+                .order_by(direct_messages::id.desc())
+                .limit(limit)
+                .select(DirectMessageEntity::as_select())
+                .load::<DirectMessageEntity>(&mut conn)?;
+
+            res.reverse();
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_last_messages(
+        &self,
+        conversation_ids: Vec<i32>,
+    ) -> Result<Vec<DirectMessageEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<DirectMessageEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = direct_messages::table
+                .filter(direct_messages::conversation_id.eq_any(conversation_ids))
+                .distinct_on(direct_messages::conversation_id)
+                .order_by((direct_messages::conversation_id, direct_messages::id.desc()))
+                .select(DirectMessageEntity::as_select())
+                .load::<DirectMessageEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn count_unread(
+        &self,
+        brawler_id: i32,
+        conversation_ids: Vec<i32>,
+    ) -> Result<Vec<DirectUnreadCountModel>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<DirectUnreadCountModel>> {
+            let mut conn = db_pool.get()?;
+            let sql = r#"
+            SELECT
+                conversation_id,
+                COUNT(*) AS unread
+            FROM
+                direct_messages
+            WHERE
+                conversation_id = ANY($2)
+                AND sender_id <> $1
+                AND read_at IS NULL
+            GROUP BY
+                conversation_id
+        "#;
+            let res = diesel::sql_query(sql)
+                .bind::<Int4, _>(brawler_id)
+                .bind::<Array<Int4>, _>(conversation_ids)
+                .load::<DirectUnreadCountModel>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn mark_read(
+        &self,
+        conversation_id: i32,
+        reader_id: i32,
+        read_at: NaiveDateTime,
+    ) -> Result<usize> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<usize> {
+            let mut conn = db_pool.get()?;
+            let updated = diesel::update(direct_messages::table)
+                .filter(direct_messages::conversation_id.eq(conversation_id))
+                .filter(direct_messages::sender_id.ne(reader_id))
+                .filter(direct_messages::read_at.is_null())
+                .set(direct_messages::read_at.eq(read_at))
+                .execute(&mut conn)?;
+            Ok(updated)
+        })
+        .await??;
+        Ok(result)
+    }
+}
@@ -1,19 +1,136 @@
 use anyhow::{Ok, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use chrono::NaiveDateTime;
+use diesel::{
+    Connection, ExpressionMethods, QueryDsl, Queryable, QueryableByName, RunQueryDsl, Selectable,
+    SelectableHelper,
+    dsl::{exists, now, update},
+    sql_types::{Array, BigInt, Float4, Int4, Nullable, Text, Timestamp},
+};
 use std::sync::Arc;
 
 use crate::{
     domain::{
-        entities::mission_chat::{MissionChatMessageWithBrawler, NewMissionChatMessageEntity},
+        entities::mission_chat::{
+            AddMissionChatMentionEntity, AddMissionChatPinEntity, AddMissionChatReactionEntity,
+            MissionChatMessageEditEntity, MissionChatMessageEntity, MissionChatMessageWithBrawler,
+            MissionChatPinEntity, MissionChatReactionEntity, MissionChatReadEntity,
+            NewMissionChatMessageEditEntity, NewMissionChatMessageEntity,
+        },
         repositories::mission_chat::MissionChatRepository,
+        value_objects::mission_chat_model::{
+            MissionChatCursor, MissionChatSearchHitModel, MissionUnreadCountModel,
+        },
     },
     infrastructure::database::{
         postgresql_connection::PgPoolSquad,
-        schema::{brawlers, mission_chat_messages},
+        schema::{
+            brawlers, mission_chat_mentions, mission_chat_message_edits,
+            mission_chat_message_reactions, mission_chat_messages, mission_chat_pins,
+            mission_chat_reads, missions,
+        },
     },
 };
 
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = mission_chat_messages)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct MessageRow {
+    id: i32,
+    mission_id: i32,
+    brawler_id: i32,
+    #[diesel(select_expression = brawlers::display_name)]
+    brawler_name: String,
+    content: String,
+    created_at: NaiveDateTime,
+    image_url: Option<String>,
+    edited_at: Option<NaiveDateTime>,
+    deleted_at: Option<NaiveDateTime>,
+    reply_to_id: Option<i32>,
+}
+
+impl MessageRow {
+    fn into_message(self) -> MissionChatMessageWithBrawler {
+        // Deleted messages stay in place as tombstones so paging is unaffected
+        let (content, image_url) = match self.deleted_at {
+            Some(_) => (String::new(), None),
+            None => (self.content, self.image_url),
+        };
+
+        MissionChatMessageWithBrawler {
+            id: self.id,
+            mission_id: self.mission_id,
+            brawler_id: self.brawler_id,
+            brawler_name: self.brawler_name,
+            content,
+            created_at: self.created_at.and_utc(),
+            image_url,
+            edited_at: self.edited_at.map(|edited| edited.and_utc()),
+            deleted_at: self.deleted_at.map(|deleted| deleted.and_utc()),
+            reply_to_id: self.reply_to_id,
+            reply_to: None,
+            reactions: Vec::new(),
+        }
+    }
+}
+
+// Control characters that have no place in chat text, used to mark the
+// matches until the excerpt has been escaped
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_STOP: char = '\u{3}';
+
+#[derive(QueryableByName)]
+struct SearchRow {
+    #[diesel(sql_type = Int4)]
+    id: i32,
+    #[diesel(sql_type = Int4)]
+    brawler_id: i32,
+    #[diesel(sql_type = Text)]
+    brawler_name: String,
+    #[diesel(sql_type = Timestamp)]
+    created_at: NaiveDateTime,
+    #[diesel(sql_type = Nullable<Int4>)]
+    reply_to_id: Option<i32>,
+    #[diesel(sql_type = Float4)]
+    rank: f32,
+    #[diesel(sql_type = Text)]
+    headline: String,
+}
+
+impl SearchRow {
+    fn into_hit(self) -> MissionChatSearchHitModel {
+        let mut highlighted = String::with_capacity(self.headline.len());
+        for c in self.headline.chars() {
+            match c {
+                HIGHLIGHT_START => highlighted.push_str("<mark>"),
+                HIGHLIGHT_STOP => highlighted.push_str("</mark>"),
+                '&' => highlighted.push_str("&amp;"),
+                '<' => highlighted.push_str("&lt;"),
+                '>' => highlighted.push_str("&gt;"),
+                '"' => highlighted.push_str("&quot;"),
+                '\'' => highlighted.push_str("&#39;"),
+                c => highlighted.push(c),
+            }
+        }
+
+        MissionChatSearchHitModel {
+            message_id: self.id,
+            brawler_id: self.brawler_id,
+            brawler_name: self.brawler_name,
+            created_at: self.created_at.and_utc(),
+            reply_to_id: self.reply_to_id,
+            rank: self.rank,
+            highlighted,
+        }
+    }
+}
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = BigInt)]
+    total: i64,
+}
+
 pub struct MissionChatPostgres {
     db_pool: Arc<PgPoolSquad>,
 }
@@ -43,45 +160,474 @@ impl MissionChatRepository for MissionChatPostgres {
     async fn get_messages_by_mission(
         &self,
         mission_id: i32,
+        reply_to_id: Option<i32>,
+        cursor: &MissionChatCursor,
+        limit: i64,
     ) -> Result<Vec<MissionChatMessageWithBrawler>> {
         let db_pool = Arc::clone(&self.db_pool);
-        let results = tokio::task::spawn_blocking(move || -> Result<Vec<(i32, i32, i32, String, String, chrono::NaiveDateTime, Option<String>)>> {
+        let cursor = cursor.clone();
+        let results = tokio::task::spawn_blocking(move || -> Result<Vec<MessageRow>> {
             let mut conn = db_pool.get()?;
-            let res = mission_chat_messages::table
+            let mut query = mission_chat_messages::table
                 .inner_join(brawlers::table)
                 .filter(mission_chat_messages::mission_id.eq(mission_id))
-                .order_by(mission_chat_messages::created_at.asc())
-                .select((
-                    mission_chat_messages::id,
-                    mission_chat_messages::mission_id,
-                    mission_chat_messages::brawler_id,
-                    brawlers::display_name,
-                    mission_chat_messages::content,
-                    mission_chat_messages::created_at,
-                    mission_chat_messages::image_url,
+                .into_boxed();
+
+            if let Some(reply_to_id) = reply_to_id {
+                query = query.filter(mission_chat_messages::reply_to_id.eq(reply_to_id));
+            }
+            if let Some(before) = cursor.before {
+                query = query.filter(mission_chat_messages::id.lt(before));
+            }
+            if let Some(after) = cursor.after {
+                query = query.filter(mission_chat_messages::id.gt(after));
+            }
+
+            // Ids follow insertion order, so they double as a stable sort key
+            query = if cursor.after.is_some() {
+                query.order_by(mission_chat_messages::id.asc())
+            } else {
+                query.order_by(mission_chat_messages::id.desc())
+            };
+
+            let mut res = query
+                .limit(limit)
+                .select(MessageRow::as_select())
+                .load::<MessageRow>(&mut conn)?;
+
+            if cursor.after.is_none() {
+                res.reverse();
+            }
+            Ok(res)
+        })
+        .await??;
+
+        Ok(results.into_iter().map(MessageRow::into_message).collect())
+    }
+
+    async fn get_messages_by_ids(
+        &self,
+        message_ids: Vec<i32>,
+    ) -> Result<Vec<MissionChatMessageWithBrawler>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let results = tokio::task::spawn_blocking(move || -> Result<Vec<MessageRow>> {
+            let mut conn = db_pool.get()?;
+            let res = mission_chat_messages::table
+                .inner_join(brawlers::table)
+                .filter(mission_chat_messages::id.eq_any(message_ids))
+                .order_by(mission_chat_messages::id.asc())
+                .select(MessageRow::as_select())
+                .load::<MessageRow>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+
+        Ok(results.into_iter().map(MessageRow::into_message).collect())
+    }
+
+    async fn get_message(&self, message_id: i32) -> Result<MissionChatMessageEntity> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<MissionChatMessageEntity> {
+            let mut conn = db_pool.get()?;
+            let res = mission_chat_messages::table
+                .filter(mission_chat_messages::id.eq(message_id))
+                .select(MissionChatMessageEntity::as_select())
+                .first::<MissionChatMessageEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn edit_message(
+        &self,
+        message_id: i32,
+        content: String,
+        edited_at: NaiveDateTime,
+    ) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+
+            conn.transaction::<(), anyhow::Error, _>(|c| {
+                // Lock the row so concurrent edits keep a complete history
+                let previous_content = mission_chat_messages::table
+                    .filter(mission_chat_messages::id.eq(message_id))
+                    .filter(mission_chat_messages::deleted_at.is_null())
+                    .select(mission_chat_messages::content)
+                    .for_update()
+                    .first::<String>(c)?;
+
+                diesel::insert_into(mission_chat_message_edits::table)
+                    .values(&NewMissionChatMessageEditEntity {
+                        message_id,
+                        previous_content,
+                        edited_at,
+                    })
+                    .execute(c)?;
+
+                update(mission_chat_messages::table)
+                    .filter(mission_chat_messages::id.eq(message_id))
+                    .set((
+                        mission_chat_messages::content.eq(content),
+                        mission_chat_messages::edited_at.eq(edited_at),
+                    ))
+                    .execute(c)?;
+
+                Ok(())
+            })?;
+
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn delete_message(&self, message_id: i32, deleted_at: NaiveDateTime) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            update(mission_chat_messages::table)
+                .filter(mission_chat_messages::id.eq(message_id))
+                .filter(mission_chat_messages::deleted_at.is_null())
+                .set(mission_chat_messages::deleted_at.eq(deleted_at))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn get_message_edits(
+        &self,
+        message_id: i32,
+    ) -> Result<Vec<MissionChatMessageEditEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Vec<MissionChatMessageEditEntity>> {
+                let mut conn = db_pool.get()?;
+                let res = mission_chat_message_edits::table
+                    .filter(mission_chat_message_edits::message_id.eq(message_id))
+                    .order_by(mission_chat_message_edits::id.asc())
+                    .select(MissionChatMessageEditEntity::as_select())
+                    .load::<MissionChatMessageEditEntity>(&mut conn)?;
+                Ok(res)
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn add_reaction(&self, reaction: AddMissionChatReactionEntity) -> Result<bool> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
+            let mut conn = db_pool.get()?;
+            let inserted = diesel::insert_into(mission_chat_message_reactions::table)
+                .values(&reaction)
+                .on_conflict_do_nothing()
+                .execute(&mut conn)?;
+            Ok(inserted > 0)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn remove_reaction(
+        &self,
+        message_id: i32,
+        brawler_id: i32,
+        emoji: String,
+    ) -> Result<bool> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
+            let mut conn = db_pool.get()?;
+            let deleted = diesel::delete(mission_chat_message_reactions::table)
+                .filter(mission_chat_message_reactions::message_id.eq(message_id))
+                .filter(mission_chat_message_reactions::brawler_id.eq(brawler_id))
+                .filter(mission_chat_message_reactions::emoji.eq(emoji))
+                .execute(&mut conn)?;
+            Ok(deleted > 0)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn count_reactions(&self, message_id: i32, emoji: String) -> Result<i64> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<i64> {
+            let mut conn = db_pool.get()?;
+            let count = mission_chat_message_reactions::table
+                .filter(mission_chat_message_reactions::message_id.eq(message_id))
+                .filter(mission_chat_message_reactions::emoji.eq(emoji))
+                .count()
+                .get_result::<i64>(&mut conn)?;
+            Ok(count)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_reactions(&self, message_ids: Vec<i32>) -> Result<Vec<MissionChatReactionEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Vec<MissionChatReactionEntity>> {
+                let mut conn = db_pool.get()?;
+                let res = mission_chat_message_reactions::table
+                    .filter(mission_chat_message_reactions::message_id.eq_any(message_ids))
+                    .order_by(mission_chat_message_reactions::created_at.asc())
+                    .select(MissionChatReactionEntity::as_select())
+                    .load::<MissionChatReactionEntity>(&mut conn)?;
+                Ok(res)
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn mark_read(&self, mission_id: i32, brawler_id: i32, message_id: i32) -> Result<i32> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<i32> {
+            let mut conn = db_pool.get()?;
+            let position = diesel::insert_into(mission_chat_reads::table)
+                .values((
+                    mission_chat_reads::mission_id.eq(mission_id),
+                    mission_chat_reads::brawler_id.eq(brawler_id),
+                    mission_chat_reads::last_read_message_id.eq(message_id),
+                ))
+                .on_conflict((mission_chat_reads::mission_id, mission_chat_reads::brawler_id))
+                .do_update()
+                .set((
+                    mission_chat_reads::last_read_message_id.eq(diesel::dsl::sql::<Int4>(
+                        "GREATEST(mission_chat_reads.last_read_message_id, EXCLUDED.last_read_message_id)",
+                    )),
+                    mission_chat_reads::read_at.eq(now),
+                ))
+                .returning(mission_chat_reads::last_read_message_id)
+                .get_result::<i32>(&mut conn)?;
+            Ok(position)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_read_positions(&self, mission_id: i32) -> Result<Vec<MissionChatReadEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<MissionChatReadEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = mission_chat_reads::table
+                .filter(mission_chat_reads::mission_id.eq(mission_id))
+                .order_by(mission_chat_reads::last_read_message_id.desc())
+                .select(MissionChatReadEntity::as_select())
+                .load::<MissionChatReadEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn count_unread(
+        &self,
+        brawler_id: i32,
+        mission_ids: Vec<i32>,
+    ) -> Result<Vec<MissionUnreadCountModel>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Vec<MissionUnreadCountModel>> {
+                let mut conn = db_pool.get()?;
+                let sql = r#"
+            SELECT
+                m.mission_id,
+                COUNT(*) AS unread
+            FROM
+                mission_chat_messages m
+            LEFT JOIN
+                mission_chat_reads r ON r.mission_id = m.mission_id AND r.brawler_id = $1
+            WHERE
+                m.mission_id = ANY($2)
+                AND m.brawler_id <> $1
+                AND m.deleted_at IS NULL
+                AND m.id > COALESCE(r.last_read_message_id, 0)
+                AND (
+                    EXISTS (
+                        SELECT 1 FROM crew_memberships cm
+                        WHERE cm.mission_id = m.mission_id AND cm.brawler_id = $1
+                    )
+                    OR EXISTS (
+                        SELECT 1 FROM missions ms
+                        WHERE ms.id = m.mission_id AND ms.chief_id = $1
+                    )
+                )
+            GROUP BY
+                m.mission_id
+        "#;
+                let res = diesel::sql_query(sql)
+                    .bind::<Int4, _>(brawler_id)
+                    .bind::<Array<Int4>, _>(mission_ids)
+                    .load::<MissionUnreadCountModel>(&mut conn)?;
+                Ok(res)
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn add_mentions(&self, mentions: Vec<AddMissionChatMentionEntity>) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            diesel::insert_into(mission_chat_mentions::table)
+                .values(&mentions)
+                .on_conflict_do_nothing()
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn pin_message(
+        &self,
+        pin: AddMissionChatPinEntity,
+        max_pins: i64,
+    ) -> Result<Option<bool>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<bool>> {
+            let mut conn = db_pool.get()?;
+
+            let res = conn.transaction::<Option<bool>, anyhow::Error, _>(|c| {
+                // Lock the mission so concurrent pins can't both slip under the cap
+                missions::table
+                    .filter(missions::id.eq(pin.mission_id))
+                    .select(missions::id)
+                    .for_update()
+                    .first::<i32>(c)?;
+
+                let already_pinned = diesel::select(exists(
+                    mission_chat_pins::table
+                        .filter(mission_chat_pins::message_id.eq(pin.message_id)),
                 ))
-                .load::<_>(&mut conn)?;
+                .get_result::<bool>(c)?;
+                if already_pinned {
+                    return Ok(Some(false));
+                }
+
+                let pins = mission_chat_pins::table
+                    .filter(mission_chat_pins::mission_id.eq(pin.mission_id))
+                    .count()
+                    .get_result::<i64>(c)?;
+                if pins >= max_pins {
+                    return Ok(None);
+                }
+
+                let inserted = diesel::insert_into(mission_chat_pins::table)
+                    .values(&pin)
+                    .on_conflict_do_nothing()
+                    .execute(c)?;
+                Ok(Some(inserted > 0))
+            })?;
+
             Ok(res)
-        }).await??;
-
-        let messages = results
-            .into_iter()
-            .map(
-                |(id, m_id, b_id, name, content, created, img)| MissionChatMessageWithBrawler {
-                    id,
-                    mission_id: m_id,
-                    brawler_id: b_id,
-                    brawler_name: name,
-                    content,
-                    created_at: chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                        created,
-                        chrono::Utc,
-                    ),
-                    image_url: img,
-                },
-            )
-            .collect();
-
-        Ok(messages)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn unpin_message(&self, message_id: i32) -> Result<bool> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
+            let mut conn = db_pool.get()?;
+            let deleted = diesel::delete(mission_chat_pins::table)
+                .filter(mission_chat_pins::message_id.eq(message_id))
+                .execute(&mut conn)?;
+            Ok(deleted > 0)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_pins(&self, mission_id: i32) -> Result<Vec<MissionChatPinEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<MissionChatPinEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = mission_chat_pins::table
+                .filter(mission_chat_pins::mission_id.eq(mission_id))
+                .order_by(mission_chat_pins::pinned_at.desc())
+                .select(MissionChatPinEntity::as_select())
+                .load::<MissionChatPinEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn search_messages(
+        &self,
+        mission_id: i32,
+        query: String,
+        page: i64,
+        page_size: i64,
+    ) -> Result<(Vec<MissionChatSearchHitModel>, i64)> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(
+            move || -> Result<(Vec<MissionChatSearchHitModel>, i64)> {
+                let mut conn = db_pool.get()?;
+                // `page` comes from the query string, so a huge one must not overflow
+                let offset = page.saturating_sub(1).saturating_mul(page_size);
+
+                let count_sql = r#"
+            SELECT
+                COUNT(*) AS total
+            FROM
+                mission_chat_messages m
+            WHERE
+                m.mission_id = $1
+                AND m.deleted_at IS NULL
+                AND m.search_vector @@ websearch_to_tsquery('simple', $2)
+        "#;
+                let total = diesel::sql_query(count_sql)
+                    .bind::<Int4, _>(mission_id)
+                    .bind::<Text, _>(query.clone())
+                    .get_result::<CountRow>(&mut conn)?
+                    .total;
+
+                let sql = r#"
+            SELECT
+                m.id,
+                m.brawler_id,
+                b.display_name AS brawler_name,
+                m.created_at,
+                m.reply_to_id,
+                ts_rank(m.search_vector, q.query) AS rank,
+                ts_headline('simple', m.content, q.query, $5) AS headline
+            FROM
+                mission_chat_messages m
+            INNER JOIN
+                brawlers b ON b.id = m.brawler_id
+            CROSS JOIN
+                websearch_to_tsquery('simple', $2) AS q(query)
+            WHERE
+                m.mission_id = $1
+                AND m.deleted_at IS NULL
+                AND m.search_vector @@ q.query
+            ORDER BY
+                rank DESC,
+                m.id DESC
+            LIMIT $3
+            OFFSET $4
+        "#;
+                let headline_options = format!(
+                    "StartSel={HIGHLIGHT_START}, StopSel={HIGHLIGHT_STOP}, \
+                     MaxFragments=2, MaxWords=20, MinWords=5, FragmentDelimiter=\" … \""
+                );
+                let rows = diesel::sql_query(sql)
+                    .bind::<Int4, _>(mission_id)
+                    .bind::<Text, _>(query)
+                    .bind::<BigInt, _>(page_size)
+                    .bind::<BigInt, _>(offset)
+                    .bind::<Text, _>(headline_options)
+                    .load::<SearchRow>(&mut conn)?;
+
+                Ok((rows.into_iter().map(SearchRow::into_hit).collect(), total))
+            },
+        )
+        .await??;
+        Ok(result)
     }
 }
@@ -0,0 +1,240 @@
+use anyhow::{Ok, Result};
+use async_trait::async_trait;
+use diesel::{dsl::now, prelude::*};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::{
+            crew_memberships::CrewMemberShips,
+            mission_join_requests::{AddMissionJoinRequestEntity, MissionJoinRequestEntity},
+        },
+        repositories::mission_join_requests::MissionJoinRequestRepository,
+        value_objects::{
+            join_request_statuses::JoinRequestStatuses,
+            mission_join_request_model::MissionJoinRequestModel,
+        },
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{brawlers, crew_memberships, mission_join_requests},
+    },
+};
+
+pub struct MissionJoinRequestPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl MissionJoinRequestPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+type JoinRequestRow = (MissionJoinRequestEntity, String, Option<String>);
+
+// Run inside the transaction of whatever makes the requests pointless, so none
+// are left pending behind it
+pub fn cancel_pending_for_mission(conn: &mut PgConnection, mission_id: i32) -> QueryResult<usize> {
+    diesel::update(mission_join_requests::table)
+        .filter(mission_join_requests::mission_id.eq(mission_id))
+        .filter(mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()))
+        .set((
+            mission_join_requests::status.eq(JoinRequestStatuses::Cancelled.to_string()),
+            mission_join_requests::decided_at.eq(now),
+        ))
+        .execute(conn)
+}
+
+// A brawler in a mission can't be approved anywhere else
+pub fn cancel_pending_for_brawler(conn: &mut PgConnection, brawler_id: i32) -> QueryResult<usize> {
+    diesel::update(mission_join_requests::table)
+        .filter(mission_join_requests::brawler_id.eq(brawler_id))
+        .filter(mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()))
+        .set((
+            mission_join_requests::status.eq(JoinRequestStatuses::Cancelled.to_string()),
+            mission_join_requests::decided_at.eq(now),
+        ))
+        .execute(conn)
+}
+
+#[async_trait]
+impl MissionJoinRequestRepository for MissionJoinRequestPostgres {
+    async fn create(&self, entity: AddMissionJoinRequestEntity) -> Result<i32> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<i32> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::insert_into(mission_join_requests::table)
+                .values(&entity)
+                .returning(mission_join_requests::id)
+                .get_result::<i32>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_by_id(&self, request_id: i32) -> Result<MissionJoinRequestEntity> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<MissionJoinRequestEntity> {
+            let mut conn = db_pool.get()?;
+            let res = mission_join_requests::table
+                .filter(mission_join_requests::id.eq(request_id))
+                .select(MissionJoinRequestEntity::as_select())
+                .first::<MissionJoinRequestEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn find_pending(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> Result<Option<MissionJoinRequestEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Option<MissionJoinRequestEntity>> {
+                let mut conn = db_pool.get()?;
+                let res = mission_join_requests::table
+                    .filter(mission_join_requests::mission_id.eq(mission_id))
+                    .filter(mission_join_requests::brawler_id.eq(brawler_id))
+                    .filter(
+                        mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()),
+                    )
+                    .select(MissionJoinRequestEntity::as_select())
+                    .first::<MissionJoinRequestEntity>(&mut conn)
+                    .optional()?;
+                Ok(res)
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn get_pending_for_mission(
+        &self,
+        mission_id: i32,
+    ) -> Result<Vec<MissionJoinRequestModel>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Vec<MissionJoinRequestModel>> {
+                let mut conn = db_pool.get()?;
+                let rows = mission_join_requests::table
+                    .inner_join(brawlers::table)
+                    .filter(mission_join_requests::mission_id.eq(mission_id))
+                    .filter(
+                        mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()),
+                    )
+                    .order_by(mission_join_requests::id.asc())
+                    .select((
+                        MissionJoinRequestEntity::as_select(),
+                        brawlers::display_name,
+                        brawlers::avatar_url,
+                    ))
+                    .load::<JoinRequestRow>(&mut conn)?;
+
+                Ok(rows
+                    .into_iter()
+                    .map(|(request, brawler_name, avatar_url)| {
+                        request.to_model(brawler_name, avatar_url)
+                    })
+                    .collect())
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn get_pending_for_brawler(
+        &self,
+        brawler_id: i32,
+    ) -> Result<Vec<MissionJoinRequestModel>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Vec<MissionJoinRequestModel>> {
+                let mut conn = db_pool.get()?;
+                let rows = mission_join_requests::table
+                    .inner_join(brawlers::table)
+                    .filter(mission_join_requests::brawler_id.eq(brawler_id))
+                    .filter(
+                        mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()),
+                    )
+                    .order_by(mission_join_requests::id.desc())
+                    .select((
+                        MissionJoinRequestEntity::as_select(),
+                        brawlers::display_name,
+                        brawlers::avatar_url,
+                    ))
+                    .load::<JoinRequestRow>(&mut conn)?;
+
+                Ok(rows
+                    .into_iter()
+                    .map(|(request, brawler_name, avatar_url)| {
+                        request.to_model(brawler_name, avatar_url)
+                    })
+                    .collect())
+            })
+            .await??;
+        Ok(result)
+    }
+
+    async fn approve(&self, request_id: i32) -> Result<bool> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
+            let mut conn = db_pool.get()?;
+
+            // The request and the membership go through together or not at all
+            conn.transaction::<bool, anyhow::Error, _>(|c| {
+                let approved = diesel::update(mission_join_requests::table)
+                    .filter(mission_join_requests::id.eq(request_id))
+                    .filter(
+                        mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()),
+                    )
+                    .set((
+                        mission_join_requests::status.eq(JoinRequestStatuses::Approved.to_string()),
+                        mission_join_requests::decided_at.eq(now),
+                    ))
+                    .returning((
+                        mission_join_requests::mission_id,
+                        mission_join_requests::brawler_id,
+                    ))
+                    .get_result::<(i32, i32)>(c)
+                    .optional()?;
+
+                let Some((mission_id, brawler_id)) = approved else {
+                    return Ok(false);
+                };
+
+                diesel::insert_into(crew_memberships::table)
+                    .values(CrewMemberShips {
+                        mission_id,
+                        brawler_id,
+                    })
+                    .execute(c)?;
+                cancel_pending_for_brawler(c, brawler_id)?;
+
+                Ok(true)
+            })
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn close(&self, request_id: i32, status: JoinRequestStatuses) -> Result<bool> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
+            let mut conn = db_pool.get()?;
+            let affected = diesel::update(mission_join_requests::table)
+                .filter(mission_join_requests::id.eq(request_id))
+                .filter(mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()))
+                .set((
+                    mission_join_requests::status.eq(status.to_string()),
+                    mission_join_requests::decided_at.eq(now),
+                ))
+                .execute(&mut conn)?;
+            Ok(affected > 0)
+        })
+        .await??;
+        Ok(result)
+    }
+}
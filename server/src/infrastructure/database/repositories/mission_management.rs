@@ -1,21 +1,35 @@
 use crate::{
     domain::{
-        entities::missions::{AddMissionEntity, EditMissionEntity},
+        entities::missions::{
+            AddMissionEntity, AddMissionStatusHistoryEntity, EditMissionEntity, MissionEntity,
+        },
         repositories::mission_management::MissionManagementRepository,
+        value_objects::mission_statuses::MissionStatuses,
     },
     infrastructure::database::{
         postgresql_connection::PgPoolSquad,
-        schema::{crew_memberships, missions},
+        repositories::mission_join_requests::cancel_pending_for_mission,
+        schema::{crew_memberships, mission_status_history, missions},
     },
 };
 use anyhow::{Ok, Result};
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use diesel::{
-    Connection, ExpressionMethods, OptionalExtension, RunQueryDsl, dsl::now, dsl::update,
+    Connection, ExpressionMethods, OptionalExtension, QueryDsl, QueryableByName, RunQueryDsl,
+    SelectableHelper,
+    dsl::{exists, not, now, update},
     insert_into,
+    sql_types::{Array, Int4, Text, Timestamp},
 };
 use std::sync::Arc;
 
+#[derive(QueryableByName)]
+struct RestoredRow {
+    #[diesel(sql_type = Int4)]
+    id: i32,
+}
+
 pub struct MissionManagementPostgres {
     db_pool: Arc<PgPoolSquad>,
 }
@@ -32,22 +46,58 @@ impl MissionManagementRepository for MissionManagementPostgres {
         let db_pool = Arc::clone(&self.db_pool);
         let result = tokio::task::spawn_blocking(move || -> Result<i32> {
             let mut conn = db_pool.get()?;
-            let res = insert_into(missions::table)
-                .values(add_mission_entity)
-                .returning(missions::id)
-                .get_result::<i32>(&mut conn)?;
+
+            // The creation is the first entry of the status history
+            let res = conn.transaction::<i32, anyhow::Error, _>(|c| {
+                let chief_id = add_mission_entity.chief_id;
+                let status = add_mission_entity.status.clone();
+                let mission_id = insert_into(missions::table)
+                    .values(add_mission_entity)
+                    .returning(missions::id)
+                    .get_result::<i32>(c)?;
+
+                insert_into(mission_status_history::table)
+                    .values(&AddMissionStatusHistoryEntity {
+                        mission_id,
+                        from_status: None,
+                        to_status: status,
+                        changed_by: Some(chief_id),
+                    })
+                    .execute(c)?;
+
+                Ok(mission_id)
+            })?;
             Ok(res)
         })
         .await??;
         Ok(result)
     }
 
-    async fn edit(&self, mission_id: i32, edit_mission_entity: EditMissionEntity) -> Result<i32> {
+    async fn find(&self, mission_id: i32) -> Result<Option<MissionEntity>> {
         let db_pool = Arc::clone(&self.db_pool);
-        let result = tokio::task::spawn_blocking(move || -> Result<i32> {
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<MissionEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = missions::table
+                .filter(missions::id.eq(mission_id))
+                .filter(missions::deleted_at.is_null())
+                .select(MissionEntity::as_select())
+                .first::<MissionEntity>(&mut conn)
+                .optional()?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn edit(
+        &self,
+        mission_id: i32,
+        edit_mission_entity: EditMissionEntity,
+    ) -> Result<Option<i32>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<i32>> {
             let mut conn = db_pool.get()?;
 
-            // First check if mission exists and belongs to this user
             let chief_id = edit_mission_entity.chief_id;
             let res = update(missions::table)
                 .filter(missions::id.eq(mission_id))
@@ -57,52 +107,126 @@ impl MissionManagementRepository for MissionManagementPostgres {
                 .returning(missions::id)
                 .get_result::<i32>(&mut conn)
                 .optional()?;
-
-            match res {
-                Some(id) => Ok(id),
-                None => Err(anyhow::anyhow!(
-                    "Mission not found or you don't have permission to edit it"
-                )),
-            }
+            Ok(res)
         })
         .await??;
 
         Ok(result)
     }
 
-    async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<()> {
+    async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<bool> {
         let db_pool = Arc::clone(&self.db_pool);
-        tokio::task::spawn_blocking(move || -> Result<()> {
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
             let mut conn = db_pool.get()?;
 
-            // Use a transaction to ensure both operations succeed or fail together
-            conn.transaction::<(), anyhow::Error, _>(|c| {
-                // First delete memberships (hard delete)
-                diesel::delete(crew_memberships::table)
-                    .filter(crew_memberships::mission_id.eq(mission_id))
-                    .execute(c)?;
+            // Memberships are kept; a brawler joining in the meantime blocks the delete
+            let other_crew = crew_memberships::table
+                .filter(crew_memberships::mission_id.eq(mission_id))
+                .filter(crew_memberships::brawler_id.ne(chief_id));
 
-                // Then soft-delete the mission
+            let removed = conn.transaction::<bool, anyhow::Error, _>(|c| {
                 let affected = update(missions::table)
                     .filter(missions::id.eq(mission_id))
                     .filter(missions::chief_id.eq(chief_id))
                     .filter(missions::deleted_at.is_null())
+                    .filter(not(exists(other_crew)))
                     .set(missions::deleted_at.eq(now))
                     .execute(c)?;
 
                 if affected == 0 {
-                    return Err(anyhow::anyhow!(
-                        "Mission not found or you don't have permission to delete it"
-                    ));
+                    return Ok(false);
                 }
 
-                Ok(())
+                cancel_pending_for_mission(c, mission_id)?;
+
+                Ok(true)
             })?;
 
-            Ok(())
+            Ok(removed)
+        })
+        .await??;
+
+        Ok(result)
+    }
+
+    async fn get_deleted(&self, chief_id: i32, since: NaiveDateTime) -> Result<Vec<MissionEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<MissionEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = missions::table
+                .filter(missions::chief_id.eq(chief_id))
+                .filter(missions::deleted_at.ge(since))
+                .order_by(missions::deleted_at.desc())
+                .select(MissionEntity::as_select())
+                .load::<MissionEntity>(&mut conn)?;
+            Ok(res)
         })
         .await??;
+        Ok(result)
+    }
 
-        Ok(())
+    async fn restore(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+        since: NaiveDateTime,
+    ) -> Result<Option<i32>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<i32>> {
+            let mut conn = db_pool.get()?;
+
+            // Checked in the same statement, so a mission created or joined in the
+            // meantime can't leave the chief with two active ones
+            let sql = r#"
+            UPDATE
+                missions m
+            SET
+                deleted_at = NULL
+            WHERE
+                m.id = $1
+                AND m.chief_id = $2
+                AND m.deleted_at >= $3
+                AND (
+                    m.status <> ALL($4)
+                    OR (
+                        NOT EXISTS (
+                            SELECT 1 FROM missions a
+                            WHERE a.chief_id = $2 AND a.deleted_at IS NULL AND a.status = ANY($4)
+                        )
+                        AND NOT EXISTS (
+                            SELECT 1 FROM crew_memberships cm
+                            INNER JOIN missions a ON a.id = cm.mission_id
+                            WHERE cm.brawler_id = $2 AND a.deleted_at IS NULL AND a.status = ANY($4)
+                        )
+                    )
+                )
+            RETURNING
+                m.id
+        "#;
+            let res = diesel::sql_query(sql)
+                .bind::<Int4, _>(mission_id)
+                .bind::<Int4, _>(chief_id)
+                .bind::<Timestamp, _>(since)
+                .bind::<Array<Text>, _>(MissionStatuses::active())
+                .get_result::<RestoredRow>(&mut conn)
+                .optional()?;
+            Ok(res.map(|row| row.id))
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn purge_deleted(&self, before: NaiveDateTime) -> Result<usize> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<usize> {
+            let mut conn = db_pool.get()?;
+            // Memberships, chat, invitations and ratings cascade
+            let affected = diesel::delete(missions::table)
+                .filter(missions::deleted_at.lt(before))
+                .execute(&mut conn)?;
+            Ok(affected)
+        })
+        .await??;
+        Ok(result)
     }
 }
@@ -2,14 +2,23 @@ use std::sync::Arc;
 
 use anyhow::{Context, Ok, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, RunQueryDsl, dsl::update};
+use chrono::NaiveDateTime;
+use diesel::{
+    Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper,
+    dsl::update,
+};
 
 use crate::{
     domain::{
+        entities::missions::{AddMissionStatusHistoryEntity, MissionEntity},
         repositories::mission_operation::MissionOperationRepository,
         value_objects::mission_statuses::MissionStatuses,
     },
-    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::missions},
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        repositories::mission_join_requests::cancel_pending_for_mission,
+        schema::{mission_status_history, missions},
+    },
 };
 pub struct MissionOperationPostgres {
     db_pool: Arc<PgPoolSquad>,
@@ -19,56 +28,135 @@ impl MissionOperationPostgres {
     pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
         Self { db_pool }
     }
+}
 
-    async fn set_status(
+#[async_trait]
+impl MissionOperationRepository for MissionOperationPostgres {
+    async fn change_status(
         &self,
         mission_id: i32,
-        chief_id: i32,
-        status: MissionStatuses,
-    ) -> Result<i32> {
+        from: MissionStatuses,
+        to: MissionStatuses,
+        changed_by: Option<i32>,
+    ) -> Result<Option<i32>> {
         let db_pool = Arc::clone(&self.db_pool);
-        let status_string = status.to_string();
-        let id = tokio::task::spawn_blocking(move || -> Result<i32> {
+        let id = tokio::task::spawn_blocking(move || -> Result<Option<i32>> {
             let mut conn = db_pool.get().context("Failed to get DB connection")?;
 
-            update(missions::table)
-                .filter(missions::id.eq(mission_id))
-                .filter(missions::chief_id.eq(chief_id))
-                .filter(missions::deleted_at.is_null())
-                .set((missions::status.eq(status_string),))
-                .returning(missions::id)
-                .get_result::<i32>(&mut conn)
-                .context("Failed to execute mission update query")
+            conn.transaction::<Option<i32>, anyhow::Error, _>(|c| {
+                let id = update(missions::table)
+                    .filter(missions::id.eq(mission_id))
+                    .filter(missions::status.eq(from.to_string()))
+                    .filter(missions::deleted_at.is_null())
+                    .set(missions::status.eq(to.to_string()))
+                    .returning(missions::id)
+                    .get_result::<i32>(c)
+                    .optional()
+                    .context("Failed to execute mission update query")?;
+
+                if id.is_some() {
+                    diesel::insert_into(mission_status_history::table)
+                        .values(&AddMissionStatusHistoryEntity {
+                            mission_id,
+                            from_status: Some(from.to_string()),
+                            to_status: to.to_string(),
+                            changed_by,
+                        })
+                        .execute(c)?;
+
+                    if !to.accepts_crew_changes() {
+                        cancel_pending_for_mission(c, mission_id)?;
+                    }
+                }
+
+                Ok(id)
+            })
         })
         .await??;
 
         Ok(id)
     }
-}
 
-#[async_trait]
-impl MissionOperationRepository for MissionOperationPostgres {
-    async fn to_progress(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let result = self
-            .set_status(mission_id, chief_id, MissionStatuses::InProgress)
-            .await?;
+    async fn cancel(
+        &self,
+        mission_id: i32,
+        from: MissionStatuses,
+        reason: String,
+        changed_by: i32,
+    ) -> Result<Option<i32>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let id = tokio::task::spawn_blocking(move || -> Result<Option<i32>> {
+            let mut conn = db_pool.get().context("Failed to get DB connection")?;
 
-        Ok(result)
-    }
+            conn.transaction::<Option<i32>, anyhow::Error, _>(|c| {
+                let id = update(missions::table)
+                    .filter(missions::id.eq(mission_id))
+                    .filter(missions::status.eq(from.to_string()))
+                    .filter(missions::deleted_at.is_null())
+                    .set((
+                        missions::status.eq(MissionStatuses::Cancelled.to_string()),
+                        missions::cancel_reason.eq(reason),
+                    ))
+                    .returning(missions::id)
+                    .get_result::<i32>(c)
+                    .optional()
+                    .context("Failed to execute mission update query")?;
 
-    async fn to_completed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let result = self
-            .set_status(mission_id, chief_id, MissionStatuses::Completed)
-            .await?;
+                if id.is_some() {
+                    diesel::insert_into(mission_status_history::table)
+                        .values(&AddMissionStatusHistoryEntity {
+                            mission_id,
+                            from_status: Some(from.to_string()),
+                            to_status: MissionStatuses::Cancelled.to_string(),
+                            changed_by: Some(changed_by),
+                        })
+                        .execute(c)?;
+                    cancel_pending_for_mission(c, mission_id)?;
+                }
 
-        Ok(result)
+                Ok(id)
+            })
+        })
+        .await??;
+
+        Ok(id)
     }
 
-    async fn to_failed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let result = self
-            .set_status(mission_id, chief_id, MissionStatuses::Failed)
-            .await?;
+    async fn get_overdue(&self, now: NaiveDateTime) -> Result<Vec<MissionEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<MissionEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = missions::table
+                .filter(missions::status.eq(MissionStatuses::InProgress.to_string()))
+                .filter(missions::deleted_at.is_null())
+                .filter(missions::deadline_at.le(now))
+                .select(MissionEntity::as_select())
+                .load::<MissionEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
 
+    async fn claim_start_reminders(
+        &self,
+        now: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Result<Vec<MissionEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<MissionEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = update(missions::table)
+                .filter(missions::status.eq(MissionStatuses::Open.to_string()))
+                .filter(missions::deleted_at.is_null())
+                .filter(missions::start_reminder_sent_at.is_null())
+                .filter(missions::scheduled_start_at.between(now, until))
+                .set(missions::start_reminder_sent_at.eq(now))
+                .returning(MissionEntity::as_returning())
+                .get_results::<MissionEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
         Ok(result)
     }
 }
@@ -2,17 +2,23 @@ use std::sync::Arc;
 
 use anyhow::{Ok, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, PgTextExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+use diesel::{
+    ExpressionMethods, NullableExpressionMethods, PgTextExpressionMethods, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
 
 use crate::{
     domain::{
-        entities::missions::MissionEntity,
+        entities::missions::{MissionEntity, MissionStatusHistoryEntity},
         repositories::mission_viewing::MissionViewingRepository,
-        value_objects::{brawler_model::BrawlerModel, mission_filter::MissionFilter},
+        value_objects::{
+            brawler_model::BrawlerModel, mission_filter::MissionFilter,
+            mission_model::MissionStatusHistoryModel,
+        },
     },
     infrastructure::database::{
         postgresql_connection::PgPoolSquad,
-        schema::{brawlers, crew_memberships, missions},
+        schema::{brawlers, crew_memberships, mission_status_history, missions},
     },
 };
 pub struct MissionViewingPostgres {
@@ -136,6 +142,7 @@ impl MissionViewingRepository for MissionViewingPostgres {
                         missions m2 ON m2.id = cm2.mission_id
                     WHERE 
                         m2.status = 'Completed'
+                        AND m2.deleted_at IS NULL
                     GROUP BY 
                         cm2.brawler_id
                 ) s ON s.brawler_id = cm.brawler_id
@@ -146,6 +153,10 @@ impl MissionViewingRepository for MissionViewingPostgres {
                         COUNT(*) AS joined_count
                     FROM 
                         crew_memberships cm3
+                    INNER JOIN 
+                        missions m3 ON m3.id = cm3.mission_id
+                    WHERE 
+                        m3.deleted_at IS NULL
                     GROUP BY 
                         cm3.brawler_id
                 ) j ON j.brawler_id = b.id
@@ -176,4 +187,28 @@ impl MissionViewingRepository for MissionViewingPostgres {
 
         Ok(name)
     }
+
+    async fn get_status_history(&self, mission_id: i32) -> Result<Vec<MissionStatusHistoryModel>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result =
+            tokio::task::spawn_blocking(move || -> Result<Vec<MissionStatusHistoryModel>> {
+                let mut conn = db_pool.get()?;
+                let rows = mission_status_history::table
+                    .left_join(brawlers::table)
+                    .filter(mission_status_history::mission_id.eq(mission_id))
+                    .order_by(mission_status_history::id.asc())
+                    .select((
+                        MissionStatusHistoryEntity::as_select(),
+                        brawlers::display_name.nullable(),
+                    ))
+                    .load::<(MissionStatusHistoryEntity, Option<String>)>(&mut conn)?;
+
+                Ok(rows
+                    .into_iter()
+                    .map(|(entry, changed_by_name)| entry.to_model(changed_by_name))
+                    .collect())
+            })
+            .await??;
+        Ok(result)
+    }
 }
@@ -1,9 +1,16 @@
 pub mod brawlers;
 pub mod crew_operation;
+pub mod direct_messages;
 pub mod friendships;
 pub mod mission_chat;
 pub mod mission_invitations;
+pub mod mission_join_requests;
 pub mod mission_management;
 pub mod mission_operation;
 pub mod mission_viewing;
+pub mod notifications;
+pub mod presence;
 pub mod rating;
+pub mod realtime_events;
+pub mod refresh_tokens;
+pub mod sessions;
@@ -0,0 +1,124 @@
+use anyhow::{Ok, Result};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::notifications::{AddNotificationEntity, NotificationEntity},
+        repositories::notifications::NotificationRepository,
+        value_objects::notification_model::NotificationFilter,
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::notifications},
+};
+
+pub struct NotificationPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl NotificationPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationPostgres {
+    async fn add(&self, entities: Vec<AddNotificationEntity>) -> Result<usize> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<usize> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::insert_into(notifications::table)
+                .values(&entities)
+                .execute(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_by_brawler(
+        &self,
+        brawler_id: i32,
+        filter: &NotificationFilter,
+        limit: i64,
+    ) -> Result<Vec<NotificationEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let filter = filter.clone();
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<NotificationEntity>> {
+            let mut conn = db_pool.get()?;
+            let mut query = notifications::table
+                .filter(notifications::brawler_id.eq(brawler_id))
+                .into_boxed();
+
+            if filter.unread_only.unwrap_or(false) {
+                query = query.filter(notifications::read_at.is_null());
+            }
+            if let Some(before) = filter.before {
+                query = query.filter(notifications::id.lt(before));
+            }
+
+            let res = query
+                .order_by(notifications::id.desc())
+                .limit(limit)
+                .select(NotificationEntity::as_select())
+                .load::<NotificationEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn mark_read(&self, brawler_id: i32, notification_id: i32) -> Result<bool> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
+            let mut conn = db_pool.get()?;
+            // Reading twice keeps the first read time
+            diesel::update(notifications::table)
+                .filter(notifications::id.eq(notification_id))
+                .filter(notifications::brawler_id.eq(brawler_id))
+                .filter(notifications::read_at.is_null())
+                .set(notifications::read_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(&mut conn)?;
+
+            let found = notifications::table
+                .filter(notifications::id.eq(notification_id))
+                .filter(notifications::brawler_id.eq(brawler_id))
+                .count()
+                .get_result::<i64>(&mut conn)?;
+            Ok(found > 0)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn mark_all_read(&self, brawler_id: i32) -> Result<usize> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<usize> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::update(notifications::table)
+                .filter(notifications::brawler_id.eq(brawler_id))
+                .filter(notifications::read_at.is_null())
+                .set(notifications::read_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn count_unread(&self, brawler_id: i32) -> Result<i64> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<i64> {
+            let mut conn = db_pool.get()?;
+            let res = notifications::table
+                .filter(notifications::brawler_id.eq(brawler_id))
+                .filter(notifications::read_at.is_null())
+                .count()
+                .get_result::<i64>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::{Ok, Result};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::{dsl::now, prelude::*};
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    domain::{
+        repositories::presence::PresenceRepository, value_objects::social_model::PresenceModel,
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{brawler_presence, brawlers},
+    },
+};
+
+pub struct PresencePostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl PresencePostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl PresenceRepository for PresencePostgres {
+    async fn connect(&self, instance_id: &str, brawler_ids: Vec<i32>) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let instance_id = instance_id.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            let rows: Vec<_> = brawler_ids
+                .iter()
+                .map(|brawler_id| {
+                    (
+                        brawler_presence::instance_id.eq(&instance_id),
+                        brawler_presence::brawler_id.eq(*brawler_id),
+                    )
+                })
+                .collect();
+
+            diesel::insert_into(brawler_presence::table)
+                .values(&rows)
+                .on_conflict((brawler_presence::instance_id, brawler_presence::brawler_id))
+                .do_update()
+                .set(brawler_presence::heartbeat_at.eq(now))
+                .execute(&mut conn)?;
+
+            diesel::update(brawlers::table)
+                .filter(brawlers::id.eq_any(&brawler_ids))
+                .set(brawlers::last_seen_at.eq(now))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn disconnect(&self, instance_id: &str, brawler_ids: Vec<i32>) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let instance_id = instance_id.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            diesel::delete(brawler_presence::table)
+                .filter(brawler_presence::instance_id.eq(&instance_id))
+                .filter(brawler_presence::brawler_id.eq_any(&brawler_ids))
+                .execute(&mut conn)?;
+
+            diesel::update(brawlers::table)
+                .filter(brawlers::id.eq_any(&brawler_ids))
+                .set(brawlers::last_seen_at.eq(now))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn heartbeat(&self, instance_id: &str) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let instance_id = instance_id.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            let brawler_ids = diesel::update(brawler_presence::table)
+                .filter(brawler_presence::instance_id.eq(&instance_id))
+                .set(brawler_presence::heartbeat_at.eq(now))
+                .returning(brawler_presence::brawler_id)
+                .get_results::<i32>(&mut conn)?;
+
+            // Keeps "last seen" close even if this instance dies without a goodbye
+            diesel::update(brawlers::table)
+                .filter(brawlers::id.eq_any(&brawler_ids))
+                .set(brawlers::last_seen_at.eq(now))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn sweep_stale(&self, stale_before: NaiveDateTime) -> Result<Vec<i32>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<i32>> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::delete(brawler_presence::table)
+                .filter(brawler_presence::heartbeat_at.lt(stale_before))
+                .returning(brawler_presence::brawler_id)
+                .get_results::<i32>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_presence(
+        &self,
+        brawler_ids: Vec<i32>,
+        online_since: NaiveDateTime,
+    ) -> Result<Vec<PresenceModel>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<PresenceModel>> {
+            let mut conn = db_pool.get()?;
+            let online: HashSet<i32> = brawler_presence::table
+                .filter(brawler_presence::brawler_id.eq_any(&brawler_ids))
+                .filter(brawler_presence::heartbeat_at.ge(online_since))
+                .select(brawler_presence::brawler_id)
+                .load::<i32>(&mut conn)?
+                .into_iter()
+                .collect();
+
+            let last_seen = brawlers::table
+                .filter(brawlers::id.eq_any(&brawler_ids))
+                .select((brawlers::id, brawlers::last_seen_at))
+                .load::<(i32, Option<NaiveDateTime>)>(&mut conn)?;
+
+            let res = last_seen
+                .into_iter()
+                .map(|(brawler_id, last_seen_at)| PresenceModel {
+                    brawler_id,
+                    online: online.contains(&brawler_id),
+                    last_seen_at: last_seen_at.map(|at| at.and_utc()),
+                })
+                .collect();
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+}
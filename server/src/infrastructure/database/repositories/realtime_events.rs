@@ -0,0 +1,94 @@
+use anyhow::{Ok, Result};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::realtime_events::{AddRealtimeEventEntity, RealtimeEventEntity},
+        repositories::realtime_events::RealtimeEventRepository,
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::realtime_events},
+};
+
+pub struct RealtimeEventPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl RealtimeEventPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl RealtimeEventRepository for RealtimeEventPostgres {
+    async fn append(&self, entity: AddRealtimeEventEntity) -> Result<i64> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<i64> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::insert_into(realtime_events::table)
+                .values(&entity)
+                .returning(realtime_events::id)
+                .get_result::<i64>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_after(&self, after_id: i64, limit: i64) -> Result<Vec<RealtimeEventEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<RealtimeEventEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = realtime_events::table
+                .filter(realtime_events::id.gt(after_id))
+                .order_by(realtime_events::id.asc())
+                .limit(limit)
+                .select(RealtimeEventEntity::as_select())
+                .load::<RealtimeEventEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn oldest_id(&self) -> Result<Option<i64>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<i64>> {
+            let mut conn = db_pool.get()?;
+            let res = realtime_events::table
+                .select(diesel::dsl::min(realtime_events::id))
+                .first::<Option<i64>>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn latest_id(&self) -> Result<Option<i64>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<i64>> {
+            let mut conn = db_pool.get()?;
+            let res = realtime_events::table
+                .select(diesel::dsl::max(realtime_events::id))
+                .first::<Option<i64>>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn prune_before(&self, id: i64) -> Result<usize> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<usize> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::delete(realtime_events::table)
+                .filter(realtime_events::id.lt(id))
+                .execute(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::{Ok, Result};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::refresh_tokens::{AddRefreshTokenEntity, RefreshTokenEntity},
+        repositories::refresh_tokens::RefreshTokenRepository,
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::refresh_tokens},
+};
+
+pub struct RefreshTokenPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl RefreshTokenPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for RefreshTokenPostgres {
+    async fn add(&self, entity: AddRefreshTokenEntity) -> Result<i32> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<i32> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::insert_into(refresh_tokens::table)
+                .values(&entity)
+                .returning(refresh_tokens::id)
+                .get_result::<i32>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn find_by_jti(&self, jti: &str) -> Result<Option<RefreshTokenEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let jti = jti.to_string();
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<RefreshTokenEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = refresh_tokens::table
+                .filter(refresh_tokens::jti.eq(jti))
+                .select(RefreshTokenEntity::as_select())
+                .first::<RefreshTokenEntity>(&mut conn)
+                .optional()?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn mark_used(&self, jti: &str) -> Result<bool> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let jti = jti.to_string();
+        let result = tokio::task::spawn_blocking(move || -> Result<bool> {
+            let mut conn = db_pool.get()?;
+            let affected = diesel::update(refresh_tokens::table)
+                .filter(refresh_tokens::jti.eq(jti))
+                .filter(refresh_tokens::used_at.is_null())
+                .filter(refresh_tokens::revoked_at.is_null())
+                .set(refresh_tokens::used_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(&mut conn)?;
+            Ok(affected > 0)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn revoke_family(&self, family_id: &str) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let family_id = family_id.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            diesel::update(refresh_tokens::table)
+                .filter(refresh_tokens::family_id.eq(family_id))
+                .filter(refresh_tokens::revoked_at.is_null())
+                .set(refresh_tokens::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn revoke_all_for_brawler(&self, brawler_id: i32) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            diesel::update(refresh_tokens::table)
+                .filter(refresh_tokens::brawler_id.eq(brawler_id))
+                .filter(refresh_tokens::revoked_at.is_null())
+                .set(refresh_tokens::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+}
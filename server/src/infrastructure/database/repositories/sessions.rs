@@ -0,0 +1,143 @@
+use anyhow::{Ok, Result};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::sessions::{AddSessionEntity, SessionEntity},
+        repositories::sessions::SessionRepository,
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::sessions},
+};
+
+pub struct SessionPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl SessionPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SessionPostgres {
+    async fn add(&self, entity: AddSessionEntity) -> Result<i32> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<i32> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::insert_into(sessions::table)
+                .values(&entity)
+                .returning(sessions::id)
+                .get_result::<i32>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn find_active_by_jti(&self, jti: &str) -> Result<Option<SessionEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let jti = jti.to_string();
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<SessionEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = sessions::table
+                .filter(sessions::jti.eq(jti))
+                .filter(sessions::revoked_at.is_null())
+                .filter(sessions::expires_at.gt(chrono::Utc::now().naive_utc()))
+                .select(SessionEntity::as_select())
+                .first::<SessionEntity>(&mut conn)
+                .optional()?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn get_active_by_brawler(&self, brawler_id: i32) -> Result<Vec<SessionEntity>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Vec<SessionEntity>> {
+            let mut conn = db_pool.get()?;
+            let res = sessions::table
+                .filter(sessions::brawler_id.eq(brawler_id))
+                .filter(sessions::revoked_at.is_null())
+                .filter(sessions::expires_at.gt(chrono::Utc::now().naive_utc()))
+                .order_by(sessions::last_used_at.desc())
+                .select(SessionEntity::as_select())
+                .load::<SessionEntity>(&mut conn)?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn touch(&self, jti: &str, expires_at: NaiveDateTime) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let jti = jti.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            diesel::update(sessions::table)
+                .filter(sessions::jti.eq(jti))
+                .filter(sessions::revoked_at.is_null())
+                .set((
+                    sessions::last_used_at.eq(chrono::Utc::now().naive_utc()),
+                    sessions::expires_at.eq(expires_at),
+                ))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn revoke(&self, brawler_id: i32, session_id: i32) -> Result<Option<String>> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let result = tokio::task::spawn_blocking(move || -> Result<Option<String>> {
+            let mut conn = db_pool.get()?;
+            let res = diesel::update(sessions::table)
+                .filter(sessions::id.eq(session_id))
+                .filter(sessions::brawler_id.eq(brawler_id))
+                .filter(sessions::revoked_at.is_null())
+                .set(sessions::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .returning(sessions::jti)
+                .get_result::<String>(&mut conn)
+                .optional()?;
+            Ok(res)
+        })
+        .await??;
+        Ok(result)
+    }
+
+    async fn revoke_by_jti(&self, jti: &str) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        let jti = jti.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            diesel::update(sessions::table)
+                .filter(sessions::jti.eq(jti))
+                .filter(sessions::revoked_at.is_null())
+                .set(sessions::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn revoke_all(&self, brawler_id: i32) -> Result<()> {
+        let db_pool = Arc::clone(&self.db_pool);
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = db_pool.get()?;
+            diesel::update(sessions::table)
+                .filter(sessions::brawler_id.eq(brawler_id))
+                .filter(sessions::revoked_at.is_null())
+                .set(sessions::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(&mut conn)?;
+            Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+}
@@ -1,5 +1,20 @@
 // @generated automatically by Diesel CLI.
 
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "tsvector", schema = "pg_catalog"))]
+    pub struct Tsvector;
+}
+
+diesel::table! {
+    brawler_presence (instance_id, brawler_id) {
+        #[max_length = 64]
+        instance_id -> Varchar,
+        brawler_id -> Int4,
+        heartbeat_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     brawlers (id) {
         id -> Int4,
@@ -20,6 +35,7 @@ diesel::table! {
         #[max_length = 255]
         cover_public_id -> Nullable<Varchar>,
         bio -> Nullable<Text>,
+        last_seen_at -> Nullable<Timestamp>,
     }
 }
 
@@ -31,6 +47,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    direct_conversations (id) {
+        id -> Int4,
+        brawler_a_id -> Int4,
+        brawler_b_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    direct_messages (id) {
+        id -> Int4,
+        conversation_id -> Int4,
+        sender_id -> Int4,
+        content -> Text,
+        created_at -> Timestamp,
+        read_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     friendships (id) {
         id -> Int4,
@@ -44,6 +80,36 @@ diesel::table! {
 }
 
 diesel::table! {
+    mission_chat_mentions (message_id, brawler_id) {
+        message_id -> Int4,
+        brawler_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mission_chat_message_edits (id) {
+        id -> Int4,
+        message_id -> Int4,
+        previous_content -> Text,
+        edited_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mission_chat_message_reactions (message_id, brawler_id, emoji) {
+        message_id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 32]
+        emoji -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
     mission_chat_messages (id) {
         id -> Int4,
         mission_id -> Int4,
@@ -51,6 +117,28 @@ diesel::table! {
         content -> Text,
         created_at -> Timestamp,
         image_url -> Nullable<Text>,
+        edited_at -> Nullable<Timestamp>,
+        deleted_at -> Nullable<Timestamp>,
+        reply_to_id -> Nullable<Int4>,
+        search_vector -> Nullable<Tsvector>,
+    }
+}
+
+diesel::table! {
+    mission_chat_pins (message_id) {
+        message_id -> Int4,
+        mission_id -> Int4,
+        pinned_by -> Int4,
+        pinned_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mission_chat_reads (mission_id, brawler_id) {
+        mission_id -> Int4,
+        brawler_id -> Int4,
+        last_read_message_id -> Int4,
+        read_at -> Timestamp,
     }
 }
 
@@ -66,6 +154,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    mission_join_requests (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 20]
+        status -> Varchar,
+        created_at -> Timestamp,
+        decided_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     mission_ratings (id) {
         id -> Int4,
@@ -77,6 +177,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    mission_status_history (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        #[max_length = 20]
+        from_status -> Nullable<Varchar>,
+        #[max_length = 20]
+        to_status -> Varchar,
+        changed_by -> Nullable<Int4>,
+        changed_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     missions (id) {
         id -> Int4,
@@ -94,24 +207,111 @@ diesel::table! {
         #[max_length = 5]
         code -> Varchar,
         max_participants -> Int4,
+        scheduled_start_at -> Nullable<Timestamp>,
+        deadline_at -> Nullable<Timestamp>,
+        start_reminder_sent_at -> Nullable<Timestamp>,
+        cancel_reason -> Nullable<Text>,
+        approval_required -> Bool,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        event -> Text,
+        read_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    realtime_events (id) {
+        id -> Int8,
+        audience -> Text,
+        payload -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    refresh_tokens (id) {
+        id -> Int4,
+        #[max_length = 64]
+        jti -> Varchar,
+        #[max_length = 64]
+        family_id -> Varchar,
+        brawler_id -> Int4,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sessions (id) {
+        id -> Int4,
+        #[max_length = 64]
+        jti -> Varchar,
+        brawler_id -> Int4,
+        user_agent -> Nullable<Text>,
+        created_at -> Timestamp,
+        last_used_at -> Timestamp,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
     }
 }
 
+diesel::joinable!(brawler_presence -> brawlers (brawler_id));
 diesel::joinable!(crew_memberships -> brawlers (brawler_id));
 diesel::joinable!(crew_memberships -> missions (mission_id));
+diesel::joinable!(direct_messages -> brawlers (sender_id));
+diesel::joinable!(direct_messages -> direct_conversations (conversation_id));
+diesel::joinable!(mission_chat_mentions -> brawlers (brawler_id));
+diesel::joinable!(mission_chat_mentions -> mission_chat_messages (message_id));
+diesel::joinable!(mission_chat_message_edits -> mission_chat_messages (message_id));
+diesel::joinable!(mission_chat_message_reactions -> brawlers (brawler_id));
+diesel::joinable!(mission_chat_message_reactions -> mission_chat_messages (message_id));
 diesel::joinable!(mission_chat_messages -> brawlers (brawler_id));
 diesel::joinable!(mission_chat_messages -> missions (mission_id));
+diesel::joinable!(mission_chat_pins -> brawlers (pinned_by));
+diesel::joinable!(mission_chat_pins -> mission_chat_messages (message_id));
+diesel::joinable!(mission_chat_pins -> missions (mission_id));
+diesel::joinable!(mission_chat_reads -> brawlers (brawler_id));
+diesel::joinable!(mission_chat_reads -> missions (mission_id));
 diesel::joinable!(mission_invitations -> missions (mission_id));
+diesel::joinable!(mission_join_requests -> brawlers (brawler_id));
+diesel::joinable!(mission_join_requests -> missions (mission_id));
 diesel::joinable!(mission_ratings -> brawlers (brawler_id));
 diesel::joinable!(mission_ratings -> missions (mission_id));
+diesel::joinable!(mission_status_history -> brawlers (changed_by));
+diesel::joinable!(mission_status_history -> missions (mission_id));
 diesel::joinable!(missions -> brawlers (chief_id));
+diesel::joinable!(notifications -> brawlers (brawler_id));
+diesel::joinable!(refresh_tokens -> brawlers (brawler_id));
+diesel::joinable!(sessions -> brawlers (brawler_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    brawler_presence,
     brawlers,
     crew_memberships,
+    direct_conversations,
+    direct_messages,
     friendships,
+    mission_chat_mentions,
+    mission_chat_message_edits,
+    mission_chat_message_reactions,
     mission_chat_messages,
+    mission_chat_pins,
+    mission_chat_reads,
     mission_invitations,
+    mission_join_requests,
     mission_ratings,
+    mission_status_history,
     missions,
+    notifications,
+    realtime_events,
+    refresh_tokens,
+    sessions,
 );
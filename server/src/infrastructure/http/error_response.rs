@@ -0,0 +1,45 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::application::errors::AppError;
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub code: String,
+    pub message: String,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let message = match &self {
+            AppError::Internal(error) => {
+                // Don't leak database or upstream details to the client
+                tracing::error!("Internal error: {:?}", error);
+                "Internal server error".to_string()
+            }
+            _ => self.to_string(),
+        };
+
+        (
+            status,
+            Json(ErrorResponse {
+                code: self.code().to_string(),
+                message,
+            }),
+        )
+            .into_response()
+    }
+}
@@ -19,7 +19,13 @@ use tracing::info;
 use crate::{
     config::config_model::DotEnvyConfig,
     infrastructure::{
-        database::postgresql_connection::PgPoolSquad, http::routers, realtime::RealtimeHub,
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::realtime_events::RealtimeEventPostgres,
+        },
+        http::routers,
+        realtime::{self, RealtimeHub, SharedRealtimeHub},
+        scheduler,
     },
 };
 
@@ -68,18 +74,31 @@ fn api_serve(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Route
             routers::mission_chat::routes(Arc::clone(&db_pool), Arc::clone(&realtime_hub)),
         )
         .nest("/rating", routers::rating::routes(Arc::clone(&db_pool)))
+        .nest(
+            "/notifications",
+            routers::notifications::routes(Arc::clone(&db_pool)),
+        )
+        .nest(
+            "/realtime",
+            routers::realtime::routes(Arc::clone(&db_pool), Arc::clone(&realtime_hub)),
+        )
         .fallback(|| async { (StatusCode::NOT_FOUND, "API not found") })
 }
 
 pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Result<()> {
-    let realtime_hub = Arc::new(RealtimeHub::new());
+    let realtime_hub = realtime_hub(&config, Arc::clone(&db_pool));
+    realtime::presence::spawn_tracker(Arc::clone(&db_pool), Arc::clone(&realtime_hub));
+    scheduler::spawn_mission_scheduler(Arc::clone(&db_pool), Arc::clone(&realtime_hub));
 
     let app = Router::new()
         .route("/", get(|| async { "Backend is alive!" }))
         .nest("/api", api_serve(Arc::clone(&db_pool), realtime_hub))
         .layer(middleware::from_fn(request_logger))
         .layer(CompressionLayer::new())
-        .layer(TimeoutLayer::new(Duration::from_secs(30)))
+        .layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            Duration::from_secs(30),
+        ))
         .layer(
             CorsLayer::new()
                 .allow_origin([
@@ -135,6 +154,18 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
     Ok(())
 }
 
+fn realtime_hub(config: &DotEnvyConfig, db_pool: Arc<PgPoolSquad>) -> SharedRealtimeHub {
+    let event_log = Arc::new(RealtimeEventPostgres::new(db_pool));
+
+    match config.realtime.backend.as_str() {
+        "postgres" => {
+            info!("Realtime events are shared through Postgres NOTIFY");
+            realtime::postgres::start_hub(config.realtime.database_url.clone(), event_log)
+        }
+        _ => RealtimeHub::start(event_log, None),
+    }
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async { tokio::signal::ctrl_c().await.expect("Fail ctrl + c") };
     let terminate = std::future::pending::<()>();
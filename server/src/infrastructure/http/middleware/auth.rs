@@ -1,17 +1,36 @@
+use std::sync::Arc;
+
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::{StatusCode, header},
     middleware::Next,
     response::Response,
 };
 
-use crate::config::config_loader::get_jwt_env;
+use crate::{
+    config::config_loader::get_jwt_env,
+    domain::repositories::sessions::SessionRepository,
+    infrastructure::{
+        database::{postgresql_connection::PgPoolSquad, repositories::sessions::SessionPostgres},
+        jwt::jwt_model::Claims,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct CurrentSession(pub String);
 
-pub async fn authorization(mut req: Request, next: Next) -> Result<Response, StatusCode> {
-    match extract_brawler_id(&req) {
-        Ok(brawler_id) => {
+pub async fn authorization(
+    State(db_pool): State<Arc<PgPoolSquad>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let token = extract_token(&req);
+    match extract_claims(db_pool, token).await {
+        Ok(claims) => {
+            let brawler_id = claims.sub;
             tracing::info!("Authorized user: {} for {}", brawler_id, req.uri());
             req.extensions_mut().insert(brawler_id);
+            req.extensions_mut().insert(CurrentSession(claims.jti));
             // Log that it was inserted
             if req.extensions().get::<i32>().is_some() {
                 tracing::info!("Extension i32 successfully inserted");
@@ -27,14 +46,20 @@ pub async fn authorization(mut req: Request, next: Next) -> Result<Response, Sta
     }
 }
 
-pub async fn optional_authorization(mut req: Request, next: Next) -> Result<Response, StatusCode> {
-    if let Ok(brawler_id) = extract_brawler_id(&req) {
-        req.extensions_mut().insert(brawler_id);
+pub async fn optional_authorization(
+    State(db_pool): State<Arc<PgPoolSquad>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let token = extract_token(&req);
+    if let Ok(claims) = extract_claims(db_pool, token).await {
+        req.extensions_mut().insert(claims.sub);
+        req.extensions_mut().insert(CurrentSession(claims.jti));
     }
     Ok(next.run(req).await)
 }
 
-fn extract_brawler_id(req: &Request) -> Result<i32, StatusCode> {
+fn extract_token(req: &Request) -> Option<String> {
     let auth_header = req
         .headers()
         .get(header::AUTHORIZATION)
@@ -47,14 +72,31 @@ fn extract_brawler_id(req: &Request) -> Result<i32, StatusCode> {
         .and_then(|q| q.split('&').find(|p| p.starts_with("token=")))
         .and_then(|p| p.strip_prefix("token="));
 
-    let token = auth_header
-        .or(query_token)
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+    auth_header.or(query_token).map(|token| token.to_string())
+}
+
+async fn extract_claims(
+    db_pool: Arc<PgPoolSquad>,
+    token: Option<String>,
+) -> Result<Claims, StatusCode> {
+    let token = token.ok_or(StatusCode::UNAUTHORIZED)?;
 
     let secret_env = get_jwt_env().map_err(|_| StatusCode::UNAUTHORIZED)?;
 
-    let claims = crate::infrastructure::jwt::verify_token(secret_env.secret, token.to_string())
+    let claims = crate::infrastructure::jwt::verify_token(secret_env.secret, token)
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
 
-    Ok(claims.sub)
+    // A valid signature is not enough: the session may have been revoked
+    let session = SessionPostgres::new(db_pool)
+        .find_active_by_jti(&claims.jti)
+        .await
+        .map_err(|e| {
+            tracing::error!("Session lookup failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    match session {
+        Some(session) if session.brawler_id == claims.sub => Ok(claims),
+        _ => Err(StatusCode::UNAUTHORIZED),
+    }
 }
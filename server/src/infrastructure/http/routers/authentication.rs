@@ -1,22 +1,36 @@
 use std::sync::Arc;
 
 use axum::{
-    Json, Router,
-    extract::State,
+    Extension, Json, Router,
+    body::Bytes,
+    extract::{Path, State},
     http::{HeaderMap, HeaderValue, StatusCode, header},
     response::IntoResponse,
-    routing::post,
+    routing::{delete, post},
 };
-use axum_extra::extract::cookie::Cookie;
+use axum_extra::extract::cookie::{Cookie, CookieJar};
 use cookie::time::Duration;
 
 use crate::{
-    application::use_cases::authentication::AuthenticationUseCase,
-    config::{config_loader::get_stage, stage::Stage},
-    domain::repositories::brawlers::BrawlerRepository,
+    application::{errors::AppError, use_cases::authentication::AuthenticationUseCase},
+    config::{
+        config_loader::{get_jwt_env, get_jwt_refresh_env, get_stage},
+        stage::Stage,
+    },
+    domain::repositories::{
+        brawlers::BrawlerRepository, refresh_tokens::RefreshTokenRepository,
+        sessions::SessionRepository,
+    },
     infrastructure::{
-        database::{postgresql_connection::PgPoolSquad, repositories::brawlers::BrawlerPostgres},
-        jwt::authentication_model::LoginModel,
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                brawlers::BrawlerPostgres, refresh_tokens::RefreshTokenPostgres,
+                sessions::SessionPostgres,
+            },
+        },
+        http::middleware::auth::{CurrentSession, authorization},
+        jwt::{authentication_model::LoginModel, jwt_model::Passport},
     },
 };
 
@@ -34,141 +48,270 @@ pub struct LineCallbackQuery {
 
 pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
     let brawlers_repository = BrawlerPostgres::new(Arc::clone(&db_pool));
-    let authentication_use_case = AuthenticationUseCase::new(Arc::new(brawlers_repository));
+    let refresh_token_repository = RefreshTokenPostgres::new(Arc::clone(&db_pool));
+    let session_repository = SessionPostgres::new(Arc::clone(&db_pool));
+    let authentication_use_case = AuthenticationUseCase::new(
+        Arc::new(brawlers_repository),
+        Arc::new(refresh_token_repository),
+        Arc::new(session_repository),
+    );
 
     let auth_routes = Router::new()
         .route("/me", axum::routing::get(get_me))
-        .layer(axum::middleware::from_fn(
-            crate::infrastructure::http::middleware::auth::authorization,
+        .route("/logout", post(logout))
+        .route(
+            "/sessions",
+            axum::routing::get(get_sessions).delete(logout_everywhere),
+        )
+        .route("/sessions/{session_id}", delete(revoke_session))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
         ));
 
     Router::new()
         .route("/login", post(login))
+        .route("/refresh", post(refresh_token))
         .route("/line/login", axum::routing::get(line_login_redirect))
         .route("/line/callback", axum::routing::get(line_callback))
         .merge(auth_routes)
         .with_state(Arc::new(authentication_use_case))
 }
 
-pub async fn login<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn login<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    headers: HeaderMap,
     Json(login_model): Json<LoginModel>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    match authentication_use_case.login(login_model).await {
-        Ok(passport) => {
-            let mut token = Cookie::build(("token", passport.access_token.clone()))
-                .path("/")
-                .same_site(cookie::SameSite::Lax)
-                .http_only(true)
-                .max_age(Duration::days(7));
-
-            let mut refresh_token = Cookie::build(("refresh_token", passport.token_type.clone()))
-                .path("/")
-                .same_site(cookie::SameSite::Lax)
-                .http_only(true)
-                .max_age(Duration::days(7));
-
-            if get_stage() == Stage::Production {
-                refresh_token = refresh_token.secure(true);
-                token = token.secure(true);
-            }
+    match authentication_use_case
+        .login(login_model, user_agent(&headers))
+        .await
+    {
+        Ok(passport) => (
+            StatusCode::OK,
+            auth_cookie_headers(&passport),
+            Json(serde_json::json!({
+                "access_token": passport.access_token,
+                "refresh_token": passport.refresh_token,
+                "token_type": passport.token_type,
+                "expires_in": passport.expires_in,
+                "message": "Login successfully"
+            })),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
 
-            let mut headers = HeaderMap::new();
-            headers.append(
-                header::SET_COOKIE,
-                HeaderValue::from_str(&token.to_string()).unwrap(),
-            );
-            headers.append(
-                header::SET_COOKIE,
-                HeaderValue::from_str(&refresh_token.to_string()).unwrap(),
-            );
+#[derive(Deserialize)]
+pub struct RefreshTokenPayload {
+    pub refresh_token: String,
+}
 
-            (
-                StatusCode::OK,
-                headers,
-                Json(serde_json::json!({
-                    "access_token": passport.access_token,
-                    "token_type": passport.token_type,
-                    "expires_in": passport.expires_in,
-                    "message": "Login successfully"
-                })),
-            )
-                .into_response()
-        }
-        Err(e) => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+pub async fn refresh_token<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    jar: CookieJar,
+    body: Bytes,
+) -> impl IntoResponse
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
+{
+    // Browsers send the http-only cookie; other clients may post the token instead
+    let refresh_token = jar
+        .get("refresh_token")
+        .map(|rft| rft.value().to_string())
+        .or_else(|| {
+            serde_json::from_slice::<RefreshTokenPayload>(&body)
+                .ok()
+                .map(|payload| payload.refresh_token)
+        });
+
+    let Some(refresh_token) = refresh_token else {
+        return AppError::Unauthorized("Refresh token not found".to_string()).into_response();
+    };
+
+    match authentication_use_case.refresh_token(refresh_token).await {
+        Ok(passport) => (
+            StatusCode::OK,
+            auth_cookie_headers(&passport),
+            Json(serde_json::json!({
+                "access_token": passport.access_token,
+                "refresh_token": passport.refresh_token,
+                "token_type": passport.token_type,
+                "expires_in": passport.expires_in,
+                "message": "Refresh token successfully"
+            })),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(255).collect())
+}
+
+fn clear_auth_cookie_headers() -> HeaderMap {
+    let mut token = Cookie::build(("token", "")).path("/");
+    let mut refresh_token = Cookie::build(("refresh_token", "")).path("/api/authentication");
+    token = token.max_age(Duration::ZERO);
+    refresh_token = refresh_token.max_age(Duration::ZERO);
+
+    let mut headers = HeaderMap::new();
+    headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&token.to_string()).unwrap(),
+    );
+    headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&refresh_token.to_string()).unwrap(),
+    );
+
+    headers
+}
+
+fn auth_cookie_headers(passport: &Passport) -> HeaderMap {
+    let access_life_time_days = get_jwt_env().map(|env| env.life_time_days).unwrap_or(7);
+    let refresh_life_time_days = get_jwt_refresh_env()
+        .map(|env| env.life_time_days)
+        .unwrap_or(30);
+
+    let mut token = Cookie::build(("token", passport.access_token.clone()))
+        .path("/")
+        .same_site(cookie::SameSite::Lax)
+        .http_only(true)
+        .max_age(Duration::days(access_life_time_days));
+
+    let mut refresh_token = Cookie::build((
+        "refresh_token",
+        passport.refresh_token.clone().unwrap_or_default(),
+    ))
+    .path("/api/authentication")
+    .same_site(cookie::SameSite::Lax)
+    .http_only(true)
+    .max_age(Duration::days(refresh_life_time_days));
+
+    if get_stage() == Stage::Production {
+        refresh_token = refresh_token.secure(true);
+        token = token.secure(true);
     }
+
+    let mut headers = HeaderMap::new();
+    headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&token.to_string()).unwrap(),
+    );
+    if passport.refresh_token.is_some() {
+        headers.append(
+            header::SET_COOKIE,
+            HeaderValue::from_str(&refresh_token.to_string()).unwrap(),
+        );
+    }
+
+    headers
 }
 
-// pub async fn refresh_token<T>(
-//     State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
-//     jar: CookieJar,
-// ) -> impl IntoResponse
-// where
-//     T: BrawlerRepository + Send + Sync,
-// {
-//     if let Some(rft) = jar.get("refresh_token") {
-//         let refresh_token = rft.value().to_string();
-
-//         let response = match authentication_use_case.refresh_token(refresh_token).await {
-//             Ok(passport) => {
-//                 let mut token = Cookie::build(("token", passport.access_token.clone()))
-//                     .path("/")
-//                     .same_site(cookie::SameSite::Lax)
-//                     .http_only(true)
-//                     .max_age(Duration::days(7));
-
-//                 let mut refresh_token =
-//                     Cookie::build(("refresh_token", passport.token_type.clone()))
-//                         .path("/")
-//                         .same_site(cookie::SameSite::Lax)
-//                         .http_only(true)
-//                         .max_age(Duration::days(7));
-
-//                 if get_stage() == Stage::Production {
-//                     token = token.secure(true);
-//                     refresh_token = refresh_token.secure(true);
-//                 }
-
-//                 let mut headers = HeaderMap::new();
-//                 headers.append(
-//                     header::SET_COOKIE,
-//                     HeaderValue::from_str(&token.to_string()).unwrap(),
-//                 );
-//                 headers.append(
-//                     header::SET_COOKIE,
-//                     HeaderValue::from_str(&refresh_token.to_string()).unwrap(),
-//                 );
-
-//                 (StatusCode::OK, headers, "Refresh token successfully").into_response()
-//             }
-//             Err(e) => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
-//         };
-//         return response;
-//     }
-
-//     (StatusCode::BAD_REQUEST, "Refresh token not found").into_response()
-// }
-
-pub async fn get_me<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
-    axum::extract::Extension(brawler_id): axum::extract::Extension<i32>,
+pub async fn get_me<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+    Extension(CurrentSession(session_id)): Extension<CurrentSession>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    match authentication_use_case.get_me(brawler_id).await {
+    match authentication_use_case
+        .get_me(brawler_id, &session_id)
+        .await
+    {
         Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn logout<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    Extension(CurrentSession(session_id)): Extension<CurrentSession>,
+) -> impl IntoResponse
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
+{
+    match authentication_use_case.logout(&session_id).await {
+        Ok(_) => (StatusCode::OK, clear_auth_cookie_headers()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_sessions<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+    Extension(CurrentSession(session_id)): Extension<CurrentSession>,
+) -> impl IntoResponse
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
+{
+    match authentication_use_case
+        .get_sessions(brawler_id, &session_id)
+        .await
+    {
+        Ok(sessions) => (StatusCode::OK, Json(sessions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn revoke_session<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+    Path(session_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
+{
+    match authentication_use_case
+        .revoke_session(brawler_id, session_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn logout_everywhere<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
+{
+    match authentication_use_case.logout_everywhere(brawler_id).await {
+        Ok(_) => (StatusCode::OK, clear_auth_cookie_headers()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub async fn line_login_redirect() -> impl IntoResponse {
     let line_env = match crate::config::config_loader::get_line_env() {
         Ok(env) => env,
-        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Config error").into_response(),
+        Err(e) => return AppError::Internal(e).into_response(),
     };
 
     let authorize_url = format!(
@@ -181,27 +324,33 @@ pub async fn line_login_redirect() -> impl IntoResponse {
     Redirect::temporary(&authorize_url).into_response()
 }
 
-pub async fn line_callback<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn line_callback<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    headers: HeaderMap,
     Query(query): Query<LineCallbackQuery>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     if let Some(err) = query.error {
-        return (
-            StatusCode::BAD_REQUEST,
-            format!("LINE Auth Error: {} - {:?}", err, query.error_description),
-        )
-            .into_response();
+        return AppError::Validation(format!(
+            "LINE Auth Error: {} - {:?}",
+            err, query.error_description
+        ))
+        .into_response();
     }
 
     let code = match query.code {
         Some(code) => code,
-        None => return (StatusCode::BAD_REQUEST, "No code provided").into_response(),
+        None => return AppError::Validation("No code provided".to_string()).into_response(),
     };
 
-    match authentication_use_case.line_login(&code).await {
+    match authentication_use_case
+        .line_login(&code, user_agent(&headers))
+        .await
+    {
         Ok(passport) => {
             let line_env = crate::config::config_loader::get_line_env().unwrap();
             let mut redirect_url = line_env.frontend_url.clone();
@@ -211,41 +360,12 @@ where
                 redirect_url.push('/');
             }
 
-            // Build cookies (Same as standard login)
-            let mut token_cookie = Cookie::build(("token", passport.access_token.clone()))
-                .path("/")
-                .same_site(cookie::SameSite::Lax)
-                .http_only(true)
-                .max_age(Duration::days(7));
-
-            let mut refresh_token_cookie =
-                Cookie::build(("refresh_token", passport.token_type.clone()))
-                    .path("/")
-                    .same_site(cookie::SameSite::Lax)
-                    .http_only(true)
-                    .max_age(Duration::days(7));
-
-            if get_stage() == Stage::Production {
-                token_cookie = token_cookie.secure(true);
-                refresh_token_cookie = refresh_token_cookie.secure(true);
-            }
-
             // Redirect to frontend with token in query string
             redirect_url.push_str(&format!("login?token={}", passport.access_token));
 
-            let mut headers = HeaderMap::new();
-            headers.append(
-                header::SET_COOKIE,
-                HeaderValue::from_str(&token_cookie.to_string()).unwrap(),
-            );
-            headers.append(
-                header::SET_COOKIE,
-                HeaderValue::from_str(&refresh_token_cookie.to_string()).unwrap(),
-            );
-
             (
                 StatusCode::TEMPORARY_REDIRECT,
-                headers,
+                auth_cookie_headers(&passport),
                 Redirect::temporary(&redirect_url),
             )
                 .into_response()
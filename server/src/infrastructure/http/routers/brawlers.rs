@@ -3,7 +3,7 @@ use std::sync::Arc;
 use axum::{
     Extension, Json, Router,
     extract::{Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
     routing::{get, post, put},
 };
@@ -12,12 +12,21 @@ use serde::Deserialize;
 use crate::{
     application::use_cases::brawlers::BrawlersUseCase,
     domain::{
-        repositories::brawlers::BrawlerRepository,
+        repositories::{
+            brawlers::BrawlerRepository, refresh_tokens::RefreshTokenRepository,
+            sessions::SessionRepository,
+        },
         value_objects::{brawler_model::RegisterBrawlerModel, uploaded_image::UploadedAvartar},
     },
     infrastructure::{
-        database::{postgresql_connection::PgPoolSquad, repositories::brawlers::BrawlerPostgres},
-        http::middleware::auth::authorization,
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                brawlers::BrawlerPostgres, refresh_tokens::RefreshTokenPostgres,
+                sessions::SessionPostgres,
+            },
+        },
+        http::middleware::auth::{CurrentSession, authorization},
     },
 };
 
@@ -30,8 +39,14 @@ pub struct SearchQuery {
 }
 
 pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
-    let brawlers_repository = BrawlerPostgres::new(db_pool);
-    let brawlers_use_case = BrawlersUseCase::new(Arc::new(brawlers_repository));
+    let brawlers_repository = BrawlerPostgres::new(Arc::clone(&db_pool));
+    let refresh_token_repository = RefreshTokenPostgres::new(Arc::clone(&db_pool));
+    let session_repository = SessionPostgres::new(Arc::clone(&db_pool));
+    let brawlers_use_case = BrawlersUseCase::new(
+        Arc::new(brawlers_repository),
+        Arc::new(refresh_token_repository),
+        Arc::new(session_repository),
+    );
 
     let protected_router = Router::new()
         .route("/avatar", post(upload_avatar))
@@ -40,7 +55,10 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         .route("/display-name", put(update_display_name))
         .route("/bio", put(update_bio))
         .route("/search", get(search))
-        .route_layer(axum::middleware::from_fn(authorization));
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ));
 
     Router::new()
         .merge(protected_router)
@@ -50,28 +68,32 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         .with_state(Arc::new(brawlers_use_case))
 }
 
-pub async fn check_username<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn check_username<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     axum::extract::Path(username): axum::extract::Path<String>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     match brawlers_use_case.check_username(username.clone()).await {
         Ok(available) => {
             tracing::info!("Username check: {} -> available: {}", username, available);
             (StatusCode::OK, Json(available)).into_response()
         }
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn search<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn search<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     Query(params): Query<SearchQuery>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     let query = params.query.unwrap_or_default();
     let page = params.current_page.unwrap_or(1);
@@ -81,38 +103,44 @@ where
         Ok(result) => (StatusCode::OK, Json(result)).into_response(),
         Err(e) => {
             tracing::error!("Search brawlers error: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
 
-pub async fn register<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn register<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
+    headers: HeaderMap,
     Json(register_brawler_model): Json<RegisterBrawlerModel>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    match brawlers_use_case.register(register_brawler_model).await {
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(255).collect());
+
+    match brawlers_use_case
+        .register(register_brawler_model, user_agent)
+        .await
+    {
         Ok(passport) => (StatusCode::CREATED, Json(passport)).into_response(),
-        Err(e) => {
-            let status = if e.to_string().contains("already taken") {
-                StatusCode::CONFLICT
-            } else {
-                StatusCode::INTERNAL_SERVER_ERROR
-            };
-            (status, e.to_string()).into_response()
-        }
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn upload_avatar<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn upload_avatar<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     Extension(brawler_id): Extension<i32>,
     Json(upload_image): Json<UploadedAvartar>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     match brawlers_use_case
         .upload_avatar(upload_image.base64_string, brawler_id)
@@ -121,18 +149,20 @@ where
         Ok(uploaded_image) => (StatusCode::CREATED, Json(uploaded_image)).into_response(),
         Err(e) => {
             tracing::error!("Upload avatar error: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
 
-pub async fn upload_cover<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn upload_cover<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     Extension(brawler_id): Extension<i32>,
     Json(upload_image): Json<UploadedAvartar>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     match brawlers_use_case
         .upload_cover(upload_image.base64_string, brawler_id)
@@ -141,39 +171,44 @@ where
         Ok(uploaded_image) => (StatusCode::CREATED, Json(uploaded_image)).into_response(),
         Err(e) => {
             tracing::error!("Upload cover error: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
 
-pub async fn get_profile<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn get_profile<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     Extension(brawler_id): Extension<i32>,
+    Extension(CurrentSession(session_id)): Extension<CurrentSession>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
-    match brawlers_use_case.get_profile(brawler_id).await {
+    match brawlers_use_case.get_profile(brawler_id, &session_id).await {
         Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
         Err(e) => {
             tracing::error!("Get profile error: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
 
-pub async fn get_profile_by_username<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn get_profile_by_username<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     axum::extract::Path(username): axum::extract::Path<String>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     match brawlers_use_case.get_profile_by_username(username).await {
         Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
         Err(e) => {
             tracing::error!("Get profile by username error: {:?}", e);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
@@ -184,22 +219,25 @@ pub struct UpdateDisplayNameRequest {
     pub display_name: String,
 }
 
-pub async fn update_display_name<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn update_display_name<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     Extension(brawler_id): Extension<i32>,
+    Extension(CurrentSession(session_id)): Extension<CurrentSession>,
     Json(request): Json<UpdateDisplayNameRequest>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     match brawlers_use_case
-        .update_display_name(brawler_id, request.display_name)
+        .update_display_name(brawler_id, &session_id, request.display_name)
         .await
     {
         Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
         Err(e) => {
             tracing::error!("Update display name error: {:?}", e);
-            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
@@ -210,19 +248,21 @@ pub struct UpdateBioRequest {
     pub bio: String,
 }
 
-pub async fn update_bio<T>(
-    State(brawlers_use_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn update_bio<T1, T2, T3>(
+    State(brawlers_use_case): State<Arc<BrawlersUseCase<T1, T2, T3>>>,
     Extension(brawler_id): Extension<i32>,
     Json(request): Json<UpdateBioRequest>,
 ) -> impl IntoResponse
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: RefreshTokenRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
 {
     match brawlers_use_case.update_bio(brawler_id, request.bio).await {
         Ok(profile) => (StatusCode::OK, Json(profile)).into_response(),
         Err(e) => {
             tracing::error!("Update bio error: {:?}", e);
-            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
@@ -6,19 +6,23 @@ use axum::{
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post},
 };
 
 use crate::{
     application::use_cases::crew_operation::CrewOperationUseCase,
     domain::repositories::{
-        crew_operation::CrewOperationRepository, mission_viewing::MissionViewingRepository,
+        crew_operation::CrewOperationRepository, mission_chat::MissionChatRepository,
+        mission_join_requests::MissionJoinRequestRepository,
+        mission_viewing::MissionViewingRepository, notifications::NotificationRepository,
     },
     infrastructure::{
         database::{
             postgresql_connection::PgPoolSquad,
             repositories::{
-                crew_operation::CrewOperationPostgres, mission_viewing::MissionViewingPostgres,
+                crew_operation::CrewOperationPostgres, mission_chat::MissionChatPostgres,
+                mission_join_requests::MissionJoinRequestPostgres,
+                mission_viewing::MissionViewingPostgres, notifications::NotificationPostgres,
             },
         },
         http::middleware::auth::authorization,
@@ -26,13 +30,21 @@ use crate::{
     },
 };
 
+type CrewOperationState<T1, T2, T3, T4, T5> = State<Arc<CrewOperationUseCase<T1, T2, T3, T4, T5>>>;
+
 pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Router {
     let crew_operation_repository = CrewOperationPostgres::new(Arc::clone(&db_pool));
     let mission_viewing_repository = MissionViewingPostgres::new(Arc::clone(&db_pool));
+    let notification_repository = NotificationPostgres::new(Arc::clone(&db_pool));
+    let mission_chat_repository = MissionChatPostgres::new(Arc::clone(&db_pool));
+    let mission_join_request_repository = MissionJoinRequestPostgres::new(Arc::clone(&db_pool));
 
     let use_case = CrewOperationUseCase::new(
         Arc::new(crew_operation_repository),
         Arc::new(mission_viewing_repository),
+        Arc::new(notification_repository),
+        Arc::new(mission_chat_repository),
+        Arc::new(mission_join_request_repository),
         realtime_hub,
     );
 
@@ -41,18 +53,38 @@ pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Rout
         .route("/leave/{mission_id}", delete(leave))
         .route("/current", get(current_mission))
         .route("/kick/{mission_id}/{brawler_id}", delete(kick))
-        .route_layer(middleware::from_fn(authorization))
+        .route("/join-requests/mine", get(my_join_requests))
+        .route(
+            "/join-requests/mission/{mission_id}",
+            post(request_to_join).get(join_requests),
+        )
+        .route("/join-requests/{request_id}", delete(cancel_join_request))
+        .route(
+            "/join-requests/{request_id}/approve",
+            patch(approve_join_request),
+        )
+        .route(
+            "/join-requests/{request_id}/reject",
+            patch(reject_join_request),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ))
         .with_state(Arc::new(use_case))
 }
 
-pub async fn join<T1, T2>(
-    State(crew_operation_use_case): State<Arc<CrewOperationUseCase<T1, T2>>>,
+pub async fn join<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
     Extension(brawler_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
 {
     match crew_operation_use_case.join(mission_id, brawler_id).await {
         Ok(_) => (
@@ -65,19 +97,22 @@ where
             .into_response(),
         Err(e) => {
             tracing::error!("Join mission error: {}", e);
-            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
 
-pub async fn leave<T1, T2>(
-    State(crew_operation_use_case): State<Arc<CrewOperationUseCase<T1, T2>>>,
+pub async fn leave<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
     Extension(brawler_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
 {
     match crew_operation_use_case.leave(mission_id, brawler_id).await {
         Ok(_) => (
@@ -88,39 +123,40 @@ where
             ),
         )
             .into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn current_mission<T1, T2>(
-    State(crew_operation_use_case): State<Arc<CrewOperationUseCase<T1, T2>>>,
+pub async fn current_mission<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
     Extension(brawler_id): Extension<i32>,
 ) -> impl IntoResponse
 where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
 {
     match crew_operation_use_case
         .get_current_mission(brawler_id)
         .await
     {
-        Ok(mission_id) => {
-            let json = serde_json::json!({
-                "mission_id": mission_id
-            });
-            (StatusCode::OK, Json(json)).into_response()
-        }
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Ok(current_mission) => (StatusCode::OK, Json(current_mission)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
-pub async fn kick<T1, T2>(
-    State(crew_operation_use_case): State<Arc<CrewOperationUseCase<T1, T2>>>,
+pub async fn kick<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
     Extension(chief_id): Extension<i32>,
     Path((mission_id, brawler_id)): Path<(i32, i32)>,
 ) -> impl IntoResponse
 where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
 {
     match crew_operation_use_case
         .kick(mission_id, chief_id, brawler_id)
@@ -134,6 +170,148 @@ where
             ),
         )
             .into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn request_to_join<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: CrewOperationRepository + Send + Sync + 'static,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
+{
+    match crew_operation_use_case
+        .request_to_join(mission_id, brawler_id)
+        .await
+    {
+        Ok(request_id) => {
+            let json_value = serde_json::json!({
+                "request_id": request_id,
+            });
+            (StatusCode::CREATED, Json(json_value)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn join_requests<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
+    Extension(chief_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: CrewOperationRepository + Send + Sync + 'static,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
+{
+    match crew_operation_use_case
+        .get_join_requests(mission_id, chief_id)
+        .await
+    {
+        Ok(requests) => (StatusCode::OK, Json(requests)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn my_join_requests<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T1: CrewOperationRepository + Send + Sync + 'static,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
+{
+    match crew_operation_use_case
+        .get_my_join_requests(brawler_id)
+        .await
+    {
+        Ok(requests) => (StatusCode::OK, Json(requests)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn cancel_join_request<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path(request_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: CrewOperationRepository + Send + Sync + 'static,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
+{
+    match crew_operation_use_case
+        .cancel_join_request(request_id, brawler_id)
+        .await
+    {
+        Ok(_) => (
+            StatusCode::OK,
+            format!("Join request id: {} has been cancelled", request_id),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn approve_join_request<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
+    Extension(chief_id): Extension<i32>,
+    Path(request_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: CrewOperationRepository + Send + Sync + 'static,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
+{
+    match crew_operation_use_case
+        .approve_join_request(request_id, chief_id)
+        .await
+    {
+        Ok(_) => (
+            StatusCode::OK,
+            format!("Join request id: {} has been approved", request_id),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn reject_join_request<T1, T2, T3, T4, T5>(
+    State(crew_operation_use_case): CrewOperationState<T1, T2, T3, T4, T5>,
+    Extension(chief_id): Extension<i32>,
+    Path(request_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: CrewOperationRepository + Send + Sync + 'static,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+    T4: MissionChatRepository + Send + Sync,
+    T5: MissionJoinRequestRepository + Send + Sync,
+{
+    match crew_operation_use_case
+        .reject_join_request(request_id, chief_id)
+        .await
+    {
+        Ok(_) => (
+            StatusCode::OK,
+            format!("Join request id: {} has been rejected", request_id),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
     }
 }
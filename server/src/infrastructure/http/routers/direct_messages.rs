@@ -0,0 +1,127 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::direct_messages::DirectMessageUseCase,
+    domain::{
+        repositories::{
+            brawlers::BrawlerRepository, direct_messages::DirectMessageRepository,
+            friendships::FriendshipRepository,
+        },
+        value_objects::direct_message_model::DirectMessageCursor,
+    },
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                brawlers::BrawlerPostgres, direct_messages::DirectMessagePostgres,
+                friendships::FriendshipPostgres,
+            },
+        },
+        http::middleware::auth::authorization,
+        realtime::RealtimeHub,
+    },
+};
+
+pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Router {
+    let direct_message_repo = DirectMessagePostgres::new(Arc::clone(&db_pool));
+    let friendship_repo = FriendshipPostgres::new(Arc::clone(&db_pool));
+    let brawler_repo = BrawlerPostgres::new(Arc::clone(&db_pool));
+
+    let use_case = DirectMessageUseCase::new(
+        Arc::new(direct_message_repo),
+        Arc::new(friendship_repo),
+        Arc::new(brawler_repo),
+        realtime_hub,
+    );
+
+    Router::new()
+        .route("/", get(get_conversations))
+        .route("/{friend_id}", get(get_messages).post(send_message))
+        .route("/{friend_id}/read", post(mark_read))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ))
+        .with_state(Arc::new(use_case))
+}
+
+#[derive(Deserialize)]
+pub struct SendDirectMessagePayload {
+    pub content: String,
+}
+
+pub async fn get_conversations<T1, T2, T3>(
+    State(use_case): State<Arc<DirectMessageUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T1: DirectMessageRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+{
+    match use_case.get_conversations(brawler_id).await {
+        Ok(conversations) => (StatusCode::OK, Json(conversations)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_messages<T1, T2, T3>(
+    State(use_case): State<Arc<DirectMessageUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+    Path(friend_id): Path<i32>,
+    Query(cursor): Query<DirectMessageCursor>,
+) -> impl IntoResponse
+where
+    T1: DirectMessageRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+{
+    match use_case.get_messages(brawler_id, friend_id, cursor).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn send_message<T1, T2, T3>(
+    State(use_case): State<Arc<DirectMessageUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+    Path(friend_id): Path<i32>,
+    Json(payload): Json<SendDirectMessagePayload>,
+) -> impl IntoResponse
+where
+    T1: DirectMessageRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+{
+    match use_case
+        .send_message(brawler_id, friend_id, payload.content)
+        .await
+    {
+        Ok(message) => (StatusCode::CREATED, Json(message)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn mark_read<T1, T2, T3>(
+    State(use_case): State<Arc<DirectMessageUseCase<T1, T2, T3>>>,
+    Extension(brawler_id): Extension<i32>,
+    Path(friend_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: DirectMessageRepository + Send + Sync,
+    T2: FriendshipRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+{
+    match use_case.mark_read(brawler_id, friend_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
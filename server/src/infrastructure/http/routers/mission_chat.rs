@@ -1,18 +1,22 @@
 use axum::{
     Extension, Json, Router,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{get, patch, post, put},
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::{
     application::use_cases::mission_chat::MissionChatUseCase,
-    domain::repositories::{
-        brawlers::BrawlerRepository, crew_operation::CrewOperationRepository,
-        mission_chat::MissionChatRepository, mission_viewing::MissionViewingRepository,
+    domain::{
+        repositories::{
+            brawlers::BrawlerRepository, crew_operation::CrewOperationRepository,
+            mission_chat::MissionChatRepository, mission_viewing::MissionViewingRepository,
+            notifications::NotificationRepository,
+        },
+        value_objects::mission_chat_model::MissionChatCursor,
     },
     infrastructure::{
         database::{
@@ -20,6 +24,7 @@ use crate::{
             repositories::{
                 brawlers::BrawlerPostgres, crew_operation::CrewOperationPostgres,
                 mission_chat::MissionChatPostgres, mission_viewing::MissionViewingPostgres,
+                notifications::NotificationPostgres,
             },
         },
         http::middleware::auth::authorization,
@@ -27,34 +32,70 @@ use crate::{
     },
 };
 
+type MissionChatState<T1, T2, T3, T4, T5> = State<Arc<MissionChatUseCase<T1, T2, T3, T4, T5>>>;
+
 pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Router {
     let mission_chat_repo = MissionChatPostgres::new(Arc::clone(&db_pool));
     let crew_repo = CrewOperationPostgres::new(Arc::clone(&db_pool));
     let mission_view_repo = MissionViewingPostgres::new(Arc::clone(&db_pool));
     let brawler_repo = BrawlerPostgres::new(Arc::clone(&db_pool));
+    let notification_repo = NotificationPostgres::new(Arc::clone(&db_pool));
 
     let use_case = MissionChatUseCase::new(
         Arc::new(mission_chat_repo),
         Arc::new(crew_repo),
         Arc::new(mission_view_repo),
         Arc::new(brawler_repo),
+        Arc::new(notification_repo),
         realtime_hub,
     );
 
     Router::new()
         .route("/{mission_id}", get(get_messages))
         .route("/{mission_id}", post(send_message))
-        .layer(axum::middleware::from_fn(authorization))
+        .route(
+            "/{mission_id}/messages/{message_id}",
+            patch(edit_message).delete(delete_message),
+        )
+        .route(
+            "/{mission_id}/messages/{message_id}/edits",
+            get(get_message_edits),
+        )
+        .route("/{mission_id}/search", get(search_messages))
+        .route("/{mission_id}/typing", post(typing))
+        .route("/{mission_id}/pins", get(get_pinned_messages))
+        .route(
+            "/{mission_id}/pins/{message_id}",
+            put(pin_message).delete(unpin_message),
+        )
+        .route("/{mission_id}/read", post(mark_read))
+        .route("/{mission_id}/reads", get(get_read_positions))
+        .route(
+            "/{mission_id}/messages/{message_id}/thread",
+            get(get_thread),
+        )
+        .route(
+            "/{mission_id}/messages/{message_id}/reactions/{emoji}",
+            put(add_reaction).delete(remove_reaction),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ))
         .with_state(Arc::new(use_case))
 }
 
 #[derive(Deserialize)]
 pub struct SendMessagePayload {
+    #[serde(default)]
     pub content: String,
+    // Base64 png or jpeg, with or without the data URL prefix
+    pub image: Option<String>,
+    pub reply_to_id: Option<i32>,
 }
 
-pub async fn send_message<T1, T2, T3, T4>(
-    State(use_case): State<Arc<MissionChatUseCase<T1, T2, T3, T4>>>,
+pub async fn send_message<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
     Extension(brawler_id): Extension<i32>,
     Path(mission_id): Path<i32>,
     Json(payload): Json<SendMessagePayload>,
@@ -64,18 +105,317 @@ where
     T2: CrewOperationRepository + Send + Sync,
     T3: MissionViewingRepository + Send + Sync,
     T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
 {
     match use_case
-        .send_message(mission_id, brawler_id, payload.content)
+        .send_message(
+            mission_id,
+            brawler_id,
+            payload.content,
+            payload.image,
+            payload.reply_to_id,
+        )
         .await
     {
         Ok(id) => (StatusCode::CREATED, Json(serde_json::json!({ "id": id }))).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMessagesQuery {
+    pub query: Option<String>,
+    pub current_page: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+pub async fn search_messages<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Query(params): Query<SearchMessagesQuery>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    let query = params.query.unwrap_or_default();
+    let page = params.current_page.unwrap_or(1);
+    let page_size = params.page_size.unwrap_or(20);
+
+    match use_case
+        .search_messages(mission_id, brawler_id, &query, page, page_size)
+        .await
+    {
+        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_messages<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Query(cursor): Query<MissionChatCursor>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case.get_messages(mission_id, brawler_id, cursor).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EditMessagePayload {
+    pub content: String,
+}
+
+pub async fn edit_message<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id)): Path<(i32, i32)>,
+    Json(payload): Json<EditMessagePayload>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .edit_message(mission_id, message_id, brawler_id, payload.content)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_message<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id)): Path<(i32, i32)>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .delete_message(mission_id, message_id, brawler_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_message_edits<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id)): Path<(i32, i32)>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .get_message_edits(mission_id, message_id, brawler_id)
+        .await
+    {
+        Ok(edits) => (StatusCode::OK, Json(edits)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn add_reaction<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id, emoji)): Path<(i32, i32, String)>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .add_reaction(mission_id, message_id, brawler_id, emoji)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn remove_reaction<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id, emoji)): Path<(i32, i32, String)>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .remove_reaction(mission_id, message_id, brawler_id, emoji)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_thread<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id)): Path<(i32, i32)>,
+    Query(cursor): Query<MissionChatCursor>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .get_thread(mission_id, message_id, brawler_id, cursor)
+        .await
+    {
+        Ok(thread) => (StatusCode::OK, Json(thread)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MarkReadPayload {
+    // Everything up to and including this message counts as read
+    pub message_id: i32,
+}
+
+pub async fn mark_read<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Json(payload): Json<MarkReadPayload>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .mark_read(mission_id, brawler_id, payload.message_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_read_positions<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case.get_read_positions(mission_id, brawler_id).await {
+        Ok(positions) => (StatusCode::OK, Json(positions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn typing<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case.typing(mission_id, brawler_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn pin_message<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id)): Path<(i32, i32)>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .pin_message(mission_id, message_id, brawler_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn unpin_message<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
+    Extension(brawler_id): Extension<i32>,
+    Path((mission_id, message_id)): Path<(i32, i32)>,
+) -> impl IntoResponse
+where
+    T1: MissionChatRepository + Send + Sync,
+    T2: CrewOperationRepository + Send + Sync,
+    T3: MissionViewingRepository + Send + Sync,
+    T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
+{
+    match use_case
+        .unpin_message(mission_id, message_id, brawler_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn get_messages<T1, T2, T3, T4>(
-    State(use_case): State<Arc<MissionChatUseCase<T1, T2, T3, T4>>>,
+pub async fn get_pinned_messages<T1, T2, T3, T4, T5>(
+    State(use_case): MissionChatState<T1, T2, T3, T4, T5>,
     Extension(brawler_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
@@ -84,9 +424,10 @@ where
     T2: CrewOperationRepository + Send + Sync,
     T3: MissionViewingRepository + Send + Sync,
     T4: BrawlerRepository + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
 {
-    match use_case.get_messages(mission_id, brawler_id).await {
-        Ok(messages) => (StatusCode::OK, Json(messages)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    match use_case.get_pinned_messages(mission_id, brawler_id).await {
+        Ok(pins) => (StatusCode::OK, Json(pins)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
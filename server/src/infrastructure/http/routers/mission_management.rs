@@ -6,7 +6,7 @@ use axum::{
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::{delete, patch, post},
+    routing::{delete, get, patch, post},
 };
 
 use crate::{
@@ -14,7 +14,7 @@ use crate::{
     domain::{
         repositories::{
             crew_operation::CrewOperationRepository,
-            mission_management::MissionManagementRepository,
+            mission_management::MissionManagementRepository, notifications::NotificationRepository,
         },
         value_objects::{
             mission_model::{AddMissionModel, EditMissionModel},
@@ -26,7 +26,7 @@ use crate::{
             postgresql_connection::PgPoolSquad,
             repositories::{
                 crew_operation::CrewOperationPostgres,
-                mission_management::MissionManagementPostgres,
+                mission_management::MissionManagementPostgres, notifications::NotificationPostgres,
             },
         },
         http::middleware::auth::authorization,
@@ -34,14 +34,15 @@ use crate::{
     },
 };
 
-pub async fn add<T1, T3>(
-    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3>>>,
+pub async fn add<T1, T3, T4>(
+    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3, T4>>>,
     Extension(brawler_id): Extension<i32>,
     Json(add_mission_model): Json<AddMissionModel>,
 ) -> impl IntoResponse
 where
     T1: MissionManagementRepository + Send + Sync,
     T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     match mission_management_use_case
         .add(brawler_id, add_mission_model)
@@ -55,13 +56,13 @@ where
         }
         Err(e) => {
             tracing::error!("Add mission error: {}", e);
-            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
 
-pub async fn edit<T1, T3>(
-    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3>>>,
+pub async fn edit<T1, T3, T4>(
+    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3, T4>>>,
     Extension(brawler_id): Extension<i32>,
     Path(mission_id): Path<i32>,
     Json(edit_mission_model): Json<EditMissionModel>,
@@ -69,6 +70,7 @@ pub async fn edit<T1, T3>(
 where
     T1: MissionManagementRepository + Send + Sync,
     T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     match mission_management_use_case
         .edit(mission_id, brawler_id, edit_mission_model)
@@ -78,18 +80,19 @@ where
             let response = format!("Edit mission success with id: {}", mission_id);
             (StatusCode::OK, response).into_response()
         }
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn remove<T1, T3>(
-    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3>>>,
+pub async fn remove<T1, T3, T4>(
+    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3, T4>>>,
     Extension(brawler_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionManagementRepository + Send + Sync,
     T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     match mission_management_use_case
         .remove(mission_id, brawler_id)
@@ -99,18 +102,56 @@ where
             let response = format!("Remove mission success with id: {}", mission_id);
             (StatusCode::OK, response).into_response()
         }
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn upload_image<T1, T3>(
-    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3>>>,
+pub async fn get_deleted<T1, T3, T4>(
+    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3, T4>>>,
+    Extension(brawler_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T1: MissionManagementRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
+{
+    match mission_management_use_case.get_deleted(brawler_id).await {
+        Ok(missions) => (StatusCode::OK, Json(missions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn restore<T1, T3, T4>(
+    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3, T4>>>,
+    Extension(brawler_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: MissionManagementRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
+{
+    match mission_management_use_case
+        .restore(mission_id, brawler_id)
+        .await
+    {
+        Ok(mission_id) => {
+            let response = format!("Restore mission success with id: {}", mission_id);
+            (StatusCode::OK, response).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn upload_image<T1, T3, T4>(
+    State(mission_management_use_case): State<Arc<MissionManagementUseCase<T1, T3, T4>>>,
     Extension(brawler_id): Extension<i32>,
     Json(upload_image): Json<UploadedAvartar>,
 ) -> impl IntoResponse
 where
     T1: MissionManagementRepository + Send + Sync,
     T3: CrewOperationRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     match mission_management_use_case
         .upload_image(upload_image.base64_string, brawler_id)
@@ -119,7 +160,7 @@ where
         Ok(uploaded_image) => (StatusCode::CREATED, Json(uploaded_image)).into_response(),
         Err(e) => {
             tracing::error!("Upload mission image error: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
@@ -127,18 +168,25 @@ where
 pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Router {
     let mission_management_repository = MissionManagementPostgres::new(Arc::clone(&db_pool));
     let crew_operation_repository = CrewOperationPostgres::new(Arc::clone(&db_pool));
+    let notification_repository = NotificationPostgres::new(Arc::clone(&db_pool));
 
     let mission_management_use_case = MissionManagementUseCase::new(
         Arc::new(mission_management_repository),
         Arc::new(crew_operation_repository),
+        Arc::new(notification_repository),
         realtime_hub,
     );
 
     Router::new()
         .route("/", post(add))
         .route("/image", post(upload_image))
+        .route("/deleted", get(get_deleted))
+        .route("/{mission_id}/restore", patch(restore))
         .route("/{mission_id}", patch(edit))
         .route("/{mission_id}", delete(remove))
-        .route_layer(middleware::from_fn(authorization))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ))
         .with_state(Arc::new(mission_management_use_case))
 }
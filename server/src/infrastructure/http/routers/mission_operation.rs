@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Router,
+    Extension, Json, Router,
     extract::{Path, State},
     http::StatusCode,
     middleware,
@@ -14,16 +14,16 @@ use crate::{
     domain::{
         repositories::{
             mission_operation::MissionOperationRepository,
-            mission_viewing::MissionViewingRepository,
+            mission_viewing::MissionViewingRepository, notifications::NotificationRepository,
         },
-        value_objects::mission_statuses::MissionStatuses,
+        value_objects::{mission_model::CancelMissionModel, mission_statuses::MissionStatuses},
     },
     infrastructure::{
         database::{
             postgresql_connection::PgPoolSquad,
             repositories::{
                 mission_operation::MissionOperationPostgres,
-                mission_viewing::MissionViewingPostgres,
+                mission_viewing::MissionViewingPostgres, notifications::NotificationPostgres,
             },
         },
         http::middleware::auth::authorization,
@@ -34,10 +34,12 @@ use crate::{
 pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Router {
     let mission_operation_repository = MissionOperationPostgres::new(Arc::clone(&db_pool));
     let mission_viewing_repository = MissionViewingPostgres::new(Arc::clone(&db_pool));
+    let notification_repository = NotificationPostgres::new(Arc::clone(&db_pool));
 
     let use_case = MissionOperationUseCase::new(
         Arc::new(mission_operation_repository),
         Arc::new(mission_viewing_repository),
+        Arc::new(notification_repository),
         realtime_hub,
     );
 
@@ -45,18 +47,23 @@ pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Rout
         .route("/in-progress/{mission_id}", patch(in_progress))
         .route("/to-completed/{mission_id}", patch(to_completed))
         .route("/to-failed/{mission_id}", patch(to_failed))
-        .route_layer(middleware::from_fn(authorization))
+        .route("/to-cancelled/{mission_id}", patch(to_cancelled))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ))
         .with_state(Arc::new(use_case))
 }
 
-pub async fn in_progress<T1, T2>(
-    State(mission_operation_use_case): State<Arc<MissionOperationUseCase<T1, T2>>>,
+pub async fn in_progress<T1, T2, T3>(
+    State(mission_operation_use_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
     Extension(chief_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     match mission_operation_use_case
         .in_progress(mission_id, chief_id)
@@ -70,18 +77,19 @@ where
             );
             (StatusCode::CREATED, response).into_response()
         }
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn to_completed<T1, T2>(
-    State(mission_operation_use_case): State<Arc<MissionOperationUseCase<T1, T2>>>,
+pub async fn to_completed<T1, T2, T3>(
+    State(mission_operation_use_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
     Extension(chief_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     match mission_operation_use_case
         .to_completed(mission_id, chief_id)
@@ -96,18 +104,19 @@ where
             ),
         )
             .into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn to_failed<T1, T2>(
-    State(mission_operation_use_case): State<Arc<MissionOperationUseCase<T1, T2>>>,
+pub async fn to_failed<T1, T2, T3>(
+    State(mission_operation_use_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
     Extension(chief_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     match mission_operation_use_case
         .to_failed(mission_id, chief_id)
@@ -122,6 +131,34 @@ where
             ),
         )
             .into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn to_cancelled<T1, T2, T3>(
+    State(mission_operation_use_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
+    Extension(chief_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Json(cancel_mission_model): Json<CancelMissionModel>,
+) -> impl IntoResponse
+where
+    T1: MissionOperationRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+{
+    match mission_operation_use_case
+        .to_cancelled(mission_id, chief_id, cancel_mission_model.reason)
+        .await
+    {
+        Ok(mission_id) => (
+            StatusCode::OK,
+            format!(
+                "Mission ({}) is now {:?}",
+                mission_id,
+                MissionStatuses::Cancelled
+            ),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
@@ -11,61 +11,106 @@ use axum::{
 use crate::{
     application::use_cases::mission_viewing::MissionViewingUseCase,
     domain::{
-        repositories::mission_viewing::MissionViewingRepository,
+        repositories::{
+            mission_chat::MissionChatRepository, mission_viewing::MissionViewingRepository,
+        },
         value_objects::mission_filter::MissionFilter,
     },
-    infrastructure::database::{
-        postgresql_connection::PgPoolSquad, repositories::mission_viewing::MissionViewingPostgres,
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                mission_chat::MissionChatPostgres, mission_viewing::MissionViewingPostgres,
+            },
+        },
+        http::middleware::auth::optional_authorization,
     },
 };
 
 pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
-    let mission_viewing_repository = MissionViewingPostgres::new(db_pool);
-    let use_case = MissionViewingUseCase::new(Arc::new(mission_viewing_repository));
+    let mission_viewing_repository = MissionViewingPostgres::new(Arc::clone(&db_pool));
+    let mission_chat_repository = MissionChatPostgres::new(Arc::clone(&db_pool));
+    let use_case = MissionViewingUseCase::new(
+        Arc::new(mission_viewing_repository),
+        Arc::new(mission_chat_repository),
+    );
 
+    // Still public; a signed in brawler additionally sees their unread chat counts
     Router::new()
         .route("/gets", get(gets))
         .route("/count/{mission_id}", get(get_mission_count))
         .route("/{mission_id}", get(view_details))
+        .route("/{mission_id}/history", get(get_status_history))
+        .route_layer(axum::middleware::from_fn_with_state(
+            db_pool,
+            optional_authorization,
+        ))
         .with_state(Arc::new(use_case))
 }
 
-pub async fn view_details<T>(
-    State(mission_viewing_use_case): State<Arc<MissionViewingUseCase<T>>>,
+pub async fn view_details<T1, T2>(
+    State(mission_viewing_use_case): State<Arc<MissionViewingUseCase<T1, T2>>>,
+    viewer: Option<Extension<i32>>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
-    T: MissionViewingRepository + Send + Sync,
+    T1: MissionViewingRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
 {
-    match mission_viewing_use_case.get_one(mission_id).await {
+    let viewer_id = viewer.map(|Extension(brawler_id)| brawler_id);
+    match mission_viewing_use_case
+        .get_one(mission_id, viewer_id)
+        .await
+    {
         Ok(mission_model) => (StatusCode::OK, Json(mission_model)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn gets<T>(
-    State(mission_viewing_use_case): State<Arc<MissionViewingUseCase<T>>>,
+pub async fn gets<T1, T2>(
+    State(mission_viewing_use_case): State<Arc<MissionViewingUseCase<T1, T2>>>,
+    viewer: Option<Extension<i32>>,
     Query(filter): Query<MissionFilter>,
 ) -> impl IntoResponse
 where
-    T: MissionViewingRepository + Send + Sync,
+    T1: MissionViewingRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
 {
     tracing::info!("Filtering missions with: {:?}", filter);
-    match mission_viewing_use_case.get_all(&filter).await {
+    let viewer_id = viewer.map(|Extension(brawler_id)| brawler_id);
+    match mission_viewing_use_case.get_all(&filter, viewer_id).await {
         Ok(mission_models) => (StatusCode::OK, Json(mission_models)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn get_mission_count<T>(
-    State(mission_viewing_use_case): State<Arc<MissionViewingUseCase<T>>>,
+pub async fn get_mission_count<T1, T2>(
+    State(mission_viewing_use_case): State<Arc<MissionViewingUseCase<T1, T2>>>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
-    T: MissionViewingRepository + Send + Sync,
+    T1: MissionViewingRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
 {
     match mission_viewing_use_case.get_mission_count(mission_id).await {
         Ok(brawler_models) => (StatusCode::OK, Json(brawler_models)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_status_history<T1, T2>(
+    State(mission_viewing_use_case): State<Arc<MissionViewingUseCase<T1, T2>>>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: MissionViewingRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
+{
+    match mission_viewing_use_case
+        .get_status_history(mission_id)
+        .await
+    {
+        Ok(history) => (StatusCode::OK, Json(history)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -2,9 +2,12 @@ pub mod authentication;
 pub mod brawlers;
 pub mod crew_operation;
 pub mod default;
+pub mod direct_messages;
 pub mod mission_chat;
 pub mod mission_management;
 pub mod mission_operation;
 pub mod mission_viewing;
+pub mod notifications;
 pub mod rating;
+pub mod realtime;
 pub mod social;
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+};
+
+use crate::{
+    application::use_cases::notifications::NotificationUseCase,
+    domain::{
+        repositories::notifications::NotificationRepository,
+        value_objects::notification_model::NotificationFilter,
+    },
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad, repositories::notifications::NotificationPostgres,
+        },
+        http::middleware::auth::authorization,
+    },
+};
+
+pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+    let notification_repository = NotificationPostgres::new(Arc::clone(&db_pool));
+    let use_case = NotificationUseCase::new(Arc::new(notification_repository));
+
+    Router::new()
+        .route("/", get(get_notifications))
+        .route("/unread-count", get(unread_count))
+        .route("/read-all", post(mark_all_read))
+        .route("/{notification_id}/read", post(mark_read))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ))
+        .with_state(Arc::new(use_case))
+}
+
+pub async fn get_notifications<T1>(
+    State(notification_use_case): State<Arc<NotificationUseCase<T1>>>,
+    Extension(brawler_id): Extension<i32>,
+    Query(filter): Query<NotificationFilter>,
+) -> impl IntoResponse
+where
+    T1: NotificationRepository + Send + Sync,
+{
+    match notification_use_case
+        .get_notifications(brawler_id, filter)
+        .await
+    {
+        Ok(notifications) => (StatusCode::OK, Json(notifications)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn unread_count<T1>(
+    State(notification_use_case): State<Arc<NotificationUseCase<T1>>>,
+    Extension(brawler_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T1: NotificationRepository + Send + Sync,
+{
+    match notification_use_case.unread_count(brawler_id).await {
+        Ok(count) => (StatusCode::OK, Json(count)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn mark_read<T1>(
+    State(notification_use_case): State<Arc<NotificationUseCase<T1>>>,
+    Extension(brawler_id): Extension<i32>,
+    Path(notification_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: NotificationRepository + Send + Sync,
+{
+    match notification_use_case
+        .mark_read(brawler_id, notification_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn mark_all_read<T1>(
+    State(notification_use_case): State<Arc<NotificationUseCase<T1>>>,
+    Extension(brawler_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T1: NotificationRepository + Send + Sync,
+{
+    match notification_use_case.mark_all_read(brawler_id).await {
+        Ok(updated) => (StatusCode::OK, Json(updated)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -44,7 +44,7 @@ where
         .await
     {
         Ok(rating_id) => (StatusCode::CREATED, Json(rating_id)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -58,7 +58,7 @@ where
 {
     match rating_use_case.get_mission_ratings(mission_id).await {
         Ok(summary) => (StatusCode::OK, Json(summary)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -76,7 +76,7 @@ where
         .await
     {
         Ok(rating) => (StatusCode::OK, Json(rating)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -93,6 +93,9 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         .route("/{mission_id}", get(get_mission_ratings))
         .route("/{mission_id}", post(add_rating))
         .route("/{mission_id}/my-rating", get(get_user_rating))
-        .route_layer(middleware::from_fn(authorization))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ))
         .with_state(Arc::new(rating_use_case))
 }
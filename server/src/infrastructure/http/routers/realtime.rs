@@ -0,0 +1,286 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use futures::{SinkExt, StreamExt, stream::SplitSink};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    application::{
+        errors::AppError,
+        use_cases::{mission_chat::MissionChatUseCase, realtime::RealtimeUseCase},
+    },
+    domain::{
+        repositories::{
+            brawlers::BrawlerRepository, crew_operation::CrewOperationRepository,
+            mission_chat::MissionChatRepository, mission_viewing::MissionViewingRepository,
+            notifications::NotificationRepository, sessions::SessionRepository,
+        },
+        value_objects::realtime::{RealtimeCommand, RealtimeEvent, RealtimeReply},
+    },
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                brawlers::BrawlerPostgres, crew_operation::CrewOperationPostgres,
+                mission_chat::MissionChatPostgres, mission_viewing::MissionViewingPostgres,
+                notifications::NotificationPostgres, sessions::SessionPostgres,
+            },
+        },
+        http::middleware::auth::{CurrentSession, authorization},
+        realtime::RealtimeHub,
+    },
+};
+
+type RealtimeState<T1, T2, T3, T4, T5, T6> = State<Arc<RealtimeUseCase<T1, T2, T3, T4, T5, T6>>>;
+
+pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Router {
+    let session_repo = SessionPostgres::new(Arc::clone(&db_pool));
+    let mission_chat_repo = MissionChatPostgres::new(Arc::clone(&db_pool));
+    let crew_repo = Arc::new(CrewOperationPostgres::new(Arc::clone(&db_pool)));
+    let mission_view_repo = MissionViewingPostgres::new(Arc::clone(&db_pool));
+    let brawler_repo = BrawlerPostgres::new(Arc::clone(&db_pool));
+    let notification_repo = NotificationPostgres::new(Arc::clone(&db_pool));
+
+    let mission_chat_use_case = MissionChatUseCase::new(
+        Arc::new(mission_chat_repo),
+        Arc::clone(&crew_repo),
+        Arc::new(mission_view_repo),
+        Arc::new(brawler_repo),
+        Arc::new(notification_repo),
+        Arc::clone(&realtime_hub),
+    );
+
+    let use_case = RealtimeUseCase::new(
+        Arc::new(session_repo),
+        crew_repo,
+        Arc::new(mission_chat_use_case),
+        realtime_hub,
+    );
+
+    let protected_routes = Router::new()
+        .route("/ticket", post(issue_ticket))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ));
+
+    // The websocket authenticates itself with the ticket
+    Router::new()
+        .route("/ws", get(websocket))
+        .merge(protected_routes)
+        .with_state(Arc::new(use_case))
+}
+
+#[derive(Deserialize)]
+pub struct WebSocketQuery {
+    pub ticket: String,
+}
+
+pub async fn issue_ticket<T1, T2, T3, T4, T5, T6>(
+    State(use_case): RealtimeState<T1, T2, T3, T4, T5, T6>,
+    Extension(brawler_id): Extension<i32>,
+    Extension(CurrentSession(session_id)): Extension<CurrentSession>,
+) -> impl IntoResponse
+where
+    T1: SessionRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
+    T4: MissionViewingRepository + Send + Sync,
+    T5: BrawlerRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+{
+    match use_case.issue_ticket(brawler_id, session_id).await {
+        Ok(ticket) => (StatusCode::CREATED, Json(ticket)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn websocket<T1, T2, T3, T4, T5, T6>(
+    State(use_case): RealtimeState<T1, T2, T3, T4, T5, T6>,
+    Query(query): Query<WebSocketQuery>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse
+where
+    T1: SessionRepository + Send + Sync + 'static,
+    T2: MissionChatRepository + Send + Sync + 'static,
+    T3: CrewOperationRepository + Send + Sync + 'static,
+    T4: MissionViewingRepository + Send + Sync + 'static,
+    T5: BrawlerRepository + Send + Sync + 'static,
+    T6: NotificationRepository + Send + Sync + 'static,
+{
+    let brawler_id = match use_case.verify_ticket(query.ticket).await {
+        Ok(brawler_id) => brawler_id,
+        Err(e) => return e.into_response(),
+    };
+
+    tracing::info!("User {} connected to realtime websocket", brawler_id);
+    ws.on_upgrade(move |socket| handle_socket(socket, use_case, brawler_id))
+}
+
+async fn handle_socket<T1, T2, T3, T4, T5, T6>(
+    socket: WebSocket,
+    use_case: Arc<RealtimeUseCase<T1, T2, T3, T4, T5, T6>>,
+    brawler_id: i32,
+) where
+    T1: SessionRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
+    T4: MissionViewingRepository + Send + Sync,
+    T5: BrawlerRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+{
+    let (mut sender, mut receiver) = socket.split();
+    let mut subscription = use_case.connect(brawler_id).await;
+    // Missions whose chat this socket asked for; the hub already checked the room
+    let mut missions: HashSet<i32> = HashSet::new();
+
+    loop {
+        tokio::select! {
+            event = subscription.recv() => match event {
+                Ok(delivery) => {
+                    if is_relevant(&delivery.event, brawler_id, &missions)
+                        && send_frame(&mut sender, &delivery.event).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Websocket of user {} lagged by {} events", brawler_id, skipped);
+                    // Some events are gone, so the client refetches instead of trusting its state
+                    if send_frame(&mut sender, &RealtimeEvent::ResyncRequired).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Closed) => break,
+            },
+            message = receiver.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let reply = match serde_json::from_str::<RealtimeCommand>(&text) {
+                        Ok(command) => {
+                            handle_command(&use_case, brawler_id, &mut missions, command).await
+                        }
+                        Err(e) => Some(error_reply(AppError::Validation(format!(
+                            "Invalid command: {}",
+                            e
+                        )))),
+                    };
+
+                    if let Some(reply) = reply
+                        && send_frame(&mut sender, &reply).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Protocol level pings are answered by axum
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    tracing::info!("User {} disconnected from realtime websocket", brawler_id);
+}
+
+async fn handle_command<T1, T2, T3, T4, T5, T6>(
+    use_case: &RealtimeUseCase<T1, T2, T3, T4, T5, T6>,
+    brawler_id: i32,
+    missions: &mut HashSet<i32>,
+    command: RealtimeCommand,
+) -> Option<RealtimeReply>
+where
+    T1: SessionRepository + Send + Sync,
+    T2: MissionChatRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
+    T4: MissionViewingRepository + Send + Sync,
+    T5: BrawlerRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+{
+    let reply = match command {
+        RealtimeCommand::SubscribeMission { mission_id } => {
+            match use_case.subscribe_mission(mission_id, brawler_id).await {
+                Ok(_) => {
+                    missions.insert(mission_id);
+                    RealtimeReply::Subscribed { mission_id }
+                }
+                Err(e) => error_reply(e),
+            }
+        }
+        RealtimeCommand::UnsubscribeMission { mission_id } => {
+            missions.remove(&mission_id);
+            RealtimeReply::Unsubscribed { mission_id }
+        }
+        RealtimeCommand::SendChatMessage {
+            mission_id,
+            content,
+            image,
+            reply_to_id,
+        } => match use_case
+            .send_message(mission_id, brawler_id, content, image, reply_to_id)
+            .await
+        {
+            Ok(message_id) => RealtimeReply::ChatMessageSent {
+                mission_id,
+                message_id,
+            },
+            Err(e) => error_reply(e),
+        },
+        RealtimeCommand::Typing { mission_id } => {
+            // Typing is fire and forget, an ack per keystroke is just noise
+            match use_case.typing(mission_id, brawler_id).await {
+                Ok(_) => return None,
+                Err(e) => error_reply(e),
+            }
+        }
+        RealtimeCommand::Ping => RealtimeReply::Pong,
+    };
+
+    Some(reply)
+}
+
+fn is_relevant(event: &RealtimeEvent, brawler_id: i32, missions: &HashSet<i32>) -> bool {
+    match event {
+        RealtimeEvent::MissionChatMessage { mission_id, .. }
+        | RealtimeEvent::MissionChatMessageEdited { mission_id, .. }
+        | RealtimeEvent::MissionChatMessageDeleted { mission_id, .. }
+        | RealtimeEvent::MissionChatReactionChanged { mission_id, .. }
+        | RealtimeEvent::MissionChatRead { mission_id, .. }
+        | RealtimeEvent::MissionPinsChanged { mission_id, .. } => missions.contains(mission_id),
+        RealtimeEvent::MissionTyping {
+            mission_id,
+            brawler_id: typing_id,
+            ..
+        } => *typing_id != brawler_id && missions.contains(mission_id),
+        _ => true,
+    }
+}
+
+fn error_reply(error: AppError) -> RealtimeReply {
+    if let AppError::Internal(e) = &error {
+        tracing::error!("Realtime command error: {:?}", e);
+    }
+
+    RealtimeReply::Error {
+        code: error.code().to_string(),
+        message: match error {
+            AppError::Internal(_) => "Internal server error".to_string(),
+            _ => error.to_string(),
+        },
+    }
+}
+
+async fn send_frame<T: Serialize>(
+    sender: &mut SplitSink<WebSocket, Message>,
+    frame: &T,
+) -> Result<(), axum::Error> {
+    let data = serde_json::to_string(frame).unwrap();
+    sender.send(Message::Text(data.into())).await
+}
@@ -1,7 +1,7 @@
 use axum::{
     Extension, Json, Router,
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{
         IntoResponse,
         sse::{Event, KeepAlive, Sse},
@@ -10,8 +10,8 @@ use axum::{
 };
 use futures::stream::Stream;
 use serde::Deserialize;
-use std::{convert::Infallible, sync::Arc};
-use tokio_stream::StreamExt;
+use std::{collections::VecDeque, convert::Infallible, sync::Arc};
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::{
     application::use_cases::social::SocialUseCase,
@@ -19,9 +19,10 @@ use crate::{
         repositories::{
             brawlers::BrawlerRepository, crew_operation::CrewOperationRepository,
             friendships::FriendshipRepository, mission_invitations::MissionInvitationRepository,
-            mission_viewing::MissionViewingRepository,
+            mission_viewing::MissionViewingRepository, notifications::NotificationRepository,
+            presence::PresenceRepository,
         },
-        value_objects::social_model::FriendshipStatusModel,
+        value_objects::{realtime::RealtimeEvent, social_model::FriendshipStatusModel},
     },
     infrastructure::{
         database::{
@@ -29,20 +30,26 @@ use crate::{
             repositories::{
                 brawlers::BrawlerPostgres, crew_operation::CrewOperationPostgres,
                 friendships::FriendshipPostgres, mission_invitations::MissionInvitationPostgres,
-                mission_viewing::MissionViewingPostgres,
+                mission_viewing::MissionViewingPostgres, notifications::NotificationPostgres,
+                presence::PresencePostgres,
             },
         },
         http::middleware::auth::{authorization, optional_authorization},
-        realtime::RealtimeHub,
+        realtime::{RealtimeDelivery, RealtimeHub, RealtimeSubscription, Replay},
     },
 };
 
+type SocialState<T1, T2, T3, T4, T5, T6, T7> =
+    State<Arc<SocialUseCase<T1, T2, T3, T4, T5, T6, T7>>>;
+
 pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Router {
     let friendship_repo = FriendshipPostgres::new(Arc::clone(&db_pool));
     let invitation_repo = MissionInvitationPostgres::new(Arc::clone(&db_pool));
     let brawlers_repo = BrawlerPostgres::new(Arc::clone(&db_pool));
     let crew_repo = CrewOperationPostgres::new(Arc::clone(&db_pool));
     let mission_repo = MissionViewingPostgres::new(Arc::clone(&db_pool));
+    let notification_repo = NotificationPostgres::new(Arc::clone(&db_pool));
+    let presence_repo = PresencePostgres::new(Arc::clone(&db_pool));
 
     let use_case = SocialUseCase::new(
         Arc::new(friendship_repo),
@@ -50,6 +57,8 @@ pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Rout
         Arc::new(brawlers_repo),
         Arc::new(mission_repo),
         Arc::new(crew_repo),
+        Arc::new(notification_repo),
+        Arc::new(presence_repo),
         Arc::clone(&realtime_hub),
     );
 
@@ -71,15 +80,25 @@ pub fn routes(db_pool: Arc<PgPoolSquad>, realtime_hub: Arc<RealtimeHub>) -> Rout
             "/invitations/respond/{invitation_id}",
             post(respond_to_invitation),
         )
-        .route_layer(axum::middleware::from_fn(authorization));
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ));
 
     Router::new()
         .route(
             "/status/{other_id}",
-            get(get_friendship_status).layer(axum::middleware::from_fn(optional_authorization)),
+            get(get_friendship_status).layer(axum::middleware::from_fn_with_state(
+                Arc::clone(&db_pool),
+                optional_authorization,
+            )),
         )
         .merge(protected_routes)
         .with_state(Arc::new(use_case))
+        .nest(
+            "/messages",
+            super::direct_messages::routes(Arc::clone(&db_pool), realtime_hub),
+        )
 }
 
 #[derive(Deserialize)]
@@ -87,8 +106,8 @@ pub struct RespondInvitation {
     pub accept: bool,
 }
 
-pub async fn get_friends<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn get_friends<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
 ) -> impl IntoResponse
 where
@@ -97,15 +116,17 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case.get_friends(user_id).await {
         Ok(friends) => (StatusCode::OK, Json(friends)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn get_pending_requests<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn get_pending_requests<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
 ) -> impl IntoResponse
 where
@@ -114,15 +135,17 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case.get_pending_requests(user_id).await {
         Ok(requests) => (StatusCode::OK, Json(requests)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn add_friend<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn add_friend<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
     Path(friend_id): Path<i32>,
 ) -> impl IntoResponse
@@ -132,15 +155,17 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case.add_friend(user_id, friend_id).await {
         Ok(id) => (StatusCode::CREATED, Json(id)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn accept_friend<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn accept_friend<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
     Path(friend_id): Path<i32>,
 ) -> impl IntoResponse
@@ -150,15 +175,17 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case.accept_friend(user_id, friend_id).await {
         Ok(_) => StatusCode::OK.into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn reject_friend<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn reject_friend<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
     Path(friend_id): Path<i32>,
 ) -> impl IntoResponse
@@ -168,15 +195,17 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case.reject_friend(user_id, friend_id).await {
         Ok(_) => StatusCode::OK.into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn invite_to_mission<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn invite_to_mission<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
     Path((invitee_id, mission_id)): Path<(i32, i32)>,
 ) -> impl IntoResponse
@@ -186,18 +215,20 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case
         .invite_to_mission(user_id, invitee_id, mission_id)
         .await
     {
         Ok(id) => (StatusCode::CREATED, Json(id)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn get_my_invitations<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn get_my_invitations<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
 ) -> impl IntoResponse
 where
@@ -206,15 +237,17 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case.get_my_invitations(user_id).await {
         Ok(invitations) => (StatusCode::OK, Json(invitations)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn respond_to_invitation<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn respond_to_invitation<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
     Path(invitation_id): Path<i32>,
     Json(payload): Json<RespondInvitation>,
@@ -225,6 +258,8 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case
         .respond_to_invitation(user_id, invitation_id, payload.accept)
@@ -235,16 +270,12 @@ where
             Json(serde_json::json!({ "mission_id": mid })),
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        )
-            .into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn get_mission_invitations<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+pub async fn get_mission_invitations<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(_user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
@@ -254,16 +285,18 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     match use_case.get_mission_invitations(mission_id).await {
         Ok(invitations) => (StatusCode::OK, Json(invitations)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn get_friendship_status<T1, T2, T3, T4, T5>(
+pub async fn get_friendship_status<T1, T2, T3, T4, T5, T6, T7>(
     Path(other_id): Path<i32>,
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     user_id_ext: Option<Extension<i32>>,
 ) -> impl IntoResponse
 where
@@ -272,6 +305,8 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     let user_id = user_id_ext.map(|Extension(id)| id).unwrap_or(0);
 
@@ -289,13 +324,13 @@ where
 
     match use_case.get_friendship_status(user_id, other_id).await {
         Ok(status) => (StatusCode::OK, Json(status)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn remove_friend<T1, T2, T3, T4, T5>(
+pub async fn remove_friend<T1, T2, T3, T4, T5, T6, T7>(
     Path(friend_id): Path<i32>,
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
     Extension(user_id): Extension<i32>,
 ) -> impl IntoResponse
 where
@@ -304,6 +339,8 @@ where
     T3: BrawlerRepository + Send + Sync,
     T4: MissionViewingRepository + Send + Sync,
     T5: CrewOperationRepository + Send + Sync,
+    T6: NotificationRepository + Send + Sync,
+    T7: PresenceRepository + Send + Sync,
 {
     tracing::info!(
         "Removing friendship: user_id={}, friend_id={}",
@@ -314,14 +351,15 @@ where
         Ok(_) => StatusCode::NO_CONTENT.into_response(),
         Err(e) => {
             tracing::error!("Failed to remove friendship: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            e.into_response()
         }
     }
 }
 
-pub async fn get_realtime_events<T1, T2, T3, T4, T5>(
-    State(use_case): State<Arc<SocialUseCase<T1, T2, T3, T4, T5>>>,
-    user_id_ext: Option<Extension<i32>>,
+pub async fn get_realtime_events<T1, T2, T3, T4, T5, T6, T7>(
+    State(use_case): SocialState<T1, T2, T3, T4, T5, T6, T7>,
+    Extension(user_id): Extension<i32>,
+    headers: HeaderMap,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>>
 where
     T1: FriendshipRepository + Send + Sync + 'static,
@@ -329,84 +367,104 @@ where
     T3: BrawlerRepository + Send + Sync + 'static,
     T4: MissionViewingRepository + Send + Sync + 'static,
     T5: CrewOperationRepository + Send + Sync + 'static,
+    T6: NotificationRepository + Send + Sync + 'static,
+    T7: PresenceRepository + Send + Sync + 'static,
 {
-    let user_id = user_id_ext.map(|Extension(id)| id).unwrap_or(0);
+    tracing::info!("User {} connected to realtime events", user_id);
+    // Browsers resend the id of the last event they saw when EventSource reconnects
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i64>().ok());
 
-    if user_id == 0 {
-        tracing::error!("Realtime connection attempted without authorization");
+    let mut events = EventStream {
+        subscription: use_case.connect_realtime(user_id).await,
+        backlog: VecDeque::new(),
+        last_id: last_event_id,
+        replayed_to: None,
+    };
+    if let Some(last_event_id) = last_event_id {
+        events.catch_up(last_event_id).await;
     }
 
-    tracing::info!("User {} connected to realtime events", user_id);
-    let rx = use_case.realtime_hub.tx.subscribe();
-    let stream = tokio_stream::wrappers::BroadcastStream::new(rx)
-        .then(move |event| {
-            let use_case = Arc::clone(&use_case);
-            async move {
-                if let Ok(event) = event {
-                    let is_relevant = match &event {
-                        crate::domain::value_objects::realtime::RealtimeEvent::FriendRequest {
-                            to_id,
-                            ..
-                        } => *to_id == user_id,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionInvitation {
-                            invitee_id,
-                            ..
-                        } => *invitee_id == user_id,
-                        crate::domain::value_objects::realtime::RealtimeEvent::FriendAccepted {
-                            to_id,
-                            ..
-                        } => *to_id == user_id,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionInvitationAccepted {
-                            inviter_id,
-                            ..
-                        } => *inviter_id == user_id,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionStatusChanged {
-                            ..
-                        } => true,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionDeleted {
-                            ..
-                        } => true,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionCreated {
-                            ..
-                        } => true,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionUpdated {
-                            ..
-                        } => true,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionJoined {
-                            ..
-                        } => true,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionLeft {
-                            ..
-                        } => true,
-                        crate::domain::value_objects::realtime::RealtimeEvent::MissionChatMessage {
-                            mission_id,
-                            brawler_id,
-                            ..
-                        } => {
-                            if user_id == 0 {
-                                false
-                            } else if *brawler_id == user_id {
-                                true
-                            } else {
-                                let is_member = use_case.crew_repo.is_member(*mission_id, user_id).await.unwrap_or(false);
-                                if is_member {
-                                    true
-                                } else {
-                                    use_case.mission_repo.get_one(*mission_id).await.map(|m| m.chief_id == user_id).unwrap_or(false)
-                                }
-                            }
-                        }
-                    };
+    // The hub only delivers events addressed to this user or their mission room
+    let stream = futures::stream::unfold(events, |mut events| async move {
+        loop {
+            if let Some(delivery) = events.backlog.pop_front() {
+                let mut event =
+                    Event::default().data(serde_json::to_string(&delivery.event).unwrap());
+                if let Some(id) = delivery.id {
+                    events.last_id = Some(id);
+                    event = event.id(id.to_string());
+                }
+                return Some((Ok(event), events));
+            }
 
-                    if is_relevant {
-                        let data = serde_json::to_string(&event).unwrap();
-                        return Some(Ok(Event::default().data(data)));
+            match events.subscription.recv().await {
+                Ok(RealtimeDelivery {
+                    event: RealtimeEvent::MissionTyping { brawler_id, .. },
+                    ..
+                }) if brawler_id == events.subscription.brawler_id() => {}
+                // Already sent while replaying
+                Ok(RealtimeDelivery { id: Some(id), .. })
+                    if events
+                        .replayed_to
+                        .is_some_and(|replayed_to| id <= replayed_to) => {}
+                Ok(delivery) => events.backlog.push_back(delivery),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "Realtime stream of user {} lagged by {} events",
+                        events.subscription.brawler_id(),
+                        skipped
+                    );
+                    match events.last_id {
+                        Some(last_id) => events.catch_up(last_id).await,
+                        None => events.resync(None),
                     }
                 }
-                None
+                Err(RecvError::Closed) => return None,
             }
-        })
-        .filter_map(|x| x);
+        }
+    });
 
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
+
+struct EventStream {
+    subscription: RealtimeSubscription,
+    // Events waiting to be written, replayed ones first
+    backlog: VecDeque<RealtimeDelivery>,
+    // Id of the last event written to this client
+    last_id: Option<i64>,
+    // Live events up to here were already covered by a replay
+    replayed_to: Option<i64>,
+}
+
+impl EventStream {
+    async fn catch_up(&mut self, after_id: i64) {
+        match self.subscription.replay(after_id).await {
+            Ok(Replay::Events(missed)) => {
+                if let Some(id) = missed.last().and_then(|delivery| delivery.id) {
+                    self.replayed_to = Some(id);
+                }
+                self.backlog.extend(missed);
+            }
+            Ok(Replay::ResyncRequired { latest_id }) => self.resync(latest_id),
+            Err(e) => {
+                tracing::error!("Failed to replay realtime events: {:?}", e);
+                self.resync(None);
+            }
+        }
+    }
+
+    // Tagged with the newest id so the next reconnect doesn't ask for the same gap
+    fn resync(&mut self, latest_id: Option<i64>) {
+        if latest_id.is_some() {
+            self.replayed_to = latest_id;
+        }
+        self.backlog.push_back(RealtimeDelivery {
+            id: latest_id,
+            event: RealtimeEvent::ResyncRequired,
+        });
+    }
+}
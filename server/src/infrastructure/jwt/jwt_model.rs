@@ -16,6 +16,8 @@ pub struct Passport {
     pub bio: Option<String>,
     pub joined_count: i64,
     pub completed_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,13 +25,34 @@ pub struct Claims {
     pub sub: i32,
     pub exp: usize,
     pub iat: usize,
+    pub jti: String,
     pub display_name: String,
     pub avatar_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshClaims {
+    pub sub: i32,
+    pub exp: usize,
+    pub iat: usize,
+    pub jti: String,
+    pub family_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealtimeTicketClaims {
+    pub sub: i32,
+    pub exp: usize,
+    pub iat: usize,
+    pub session_id: String,
+    pub scope: String,
+}
+
 impl Passport {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         brawler_id: i32,
+        session_id: String,
         display_name: String,
         username: String,
         avatar_url: Option<String>,
@@ -46,6 +69,7 @@ impl Passport {
             sub: brawler_id,
             exp: expires_in,
             iat: Utc::now().timestamp() as usize,
+            jti: session_id,
             display_name: display_name.clone(),
             avatar_url: avatar_url.clone(),
         };
@@ -64,6 +88,7 @@ impl Passport {
             bio,
             joined_count,
             completed_count,
+            refresh_token: None,
         }
     }
 }
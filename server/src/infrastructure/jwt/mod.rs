@@ -23,3 +23,53 @@ pub fn verify_token(secret: String, token: String) -> Result<jwt_model::Claims>
 
     Ok(token.claims)
 }
+
+pub fn generate_refresh_token(secret: String, claims: &jwt_model::RefreshClaims) -> Result<String> {
+    let token = encode(
+        &Header::default(),
+        claims,
+        &EncodingKey::from_secret(secret.as_ref()),
+    )?;
+
+    Ok(token)
+}
+
+pub fn verify_refresh_token(secret: String, token: String) -> Result<jwt_model::RefreshClaims> {
+    let token = decode::<jwt_model::RefreshClaims>(
+        &token,
+        &DecodingKey::from_secret(secret.as_ref()),
+        &Validation::default(),
+    )?;
+
+    Ok(token.claims)
+}
+
+pub fn generate_realtime_ticket(
+    secret: String,
+    claims: &jwt_model::RealtimeTicketClaims,
+) -> Result<String> {
+    let token = encode(
+        &Header::default(),
+        claims,
+        &EncodingKey::from_secret(secret.as_ref()),
+    )?;
+
+    Ok(token)
+}
+
+pub fn verify_realtime_ticket(
+    secret: String,
+    ticket: String,
+) -> Result<jwt_model::RealtimeTicketClaims> {
+    // Tickets live for seconds, the default 60s leeway would outlast them
+    let mut validation = Validation::default();
+    validation.leeway = 0;
+
+    let token = decode::<jwt_model::RealtimeTicketClaims>(
+        &ticket,
+        &DecodingKey::from_secret(secret.as_ref()),
+        &validation,
+    )?;
+
+    Ok(token.claims)
+}
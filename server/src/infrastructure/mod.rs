@@ -5,3 +5,4 @@ pub mod http;
 pub mod jwt;
 pub mod line;
 pub mod realtime;
+pub mod scheduler;
@@ -0,0 +1,421 @@
+pub mod postgres;
+pub mod presence;
+
+use crate::domain::{
+    entities::realtime_events::AddRealtimeEventEntity,
+    repositories::realtime_events::RealtimeEventRepository,
+    value_objects::realtime::{Audience, RealtimeEvent},
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, RwLock, Weak},
+    time::{Duration, Instant},
+};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc,
+};
+
+const INBOX_CAPACITY: usize = 100;
+// The log keeps roughly this many events, trimmed every PRUNE_INTERVAL appends
+const RETAINED_EVENTS: i64 = 10_000;
+const PRUNE_INTERVAL: i64 = 500;
+// A client further behind than this is told to resync instead
+const MAX_REPLAY_EVENTS: i64 = 500;
+
+struct Inbox {
+    tx: broadcast::Sender<RealtimeDelivery>,
+    connections: usize,
+}
+
+#[derive(Default)]
+struct HubState {
+    // One inbox per connected brawler, shared by all of their tabs
+    inboxes: HashMap<i32, Inbox>,
+    // mission_id -> connected brawlers in that mission
+    rooms: HashMap<i32, HashSet<i32>>,
+    // brawler_id -> rooms they sit in, so a disconnect can clean up
+    memberships: HashMap<i32, HashSet<i32>>,
+}
+
+// An event as handed to a connection. Transient events, or ones the log failed
+// to store, carry no id.
+#[derive(Debug, Clone)]
+pub struct RealtimeDelivery {
+    pub id: Option<i64>,
+    pub event: RealtimeEvent,
+}
+
+pub enum Replay {
+    Events(Vec<RealtimeDelivery>),
+    ResyncRequired { latest_id: Option<i64> },
+}
+
+// What travels between instances: the event plus who it is for, tagged with
+// the instance that produced it so the sender can skip its own echo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealtimeMessage {
+    pub origin: String,
+    pub id: Option<i64>,
+    pub audience: Audience,
+    pub event: RealtimeEvent,
+}
+
+// Fans events out to the other instances. Local delivery never waits on it.
+pub trait RealtimeBackend: Send + Sync {
+    fn publish(&self, message: &RealtimeMessage);
+}
+
+pub type SharedRealtimeEventLog = Arc<dyn RealtimeEventRepository + Send + Sync>;
+
+pub struct RealtimeHub {
+    instance_id: String,
+    state: RwLock<HubState>,
+    backend: Option<Box<dyn RealtimeBackend>>,
+    event_log: SharedRealtimeEventLog,
+    pending: mpsc::UnboundedSender<(Audience, RealtimeEvent)>,
+    // (mission_id, brawler_id) -> last typing event let through, shared by the
+    // HTTP route and the websocket so both count against the same throttle
+    typing_sent: Mutex<HashMap<(i32, i32), Instant>>,
+}
+
+impl RealtimeHub {
+    // Without a backend events never leave this process
+    pub fn start(
+        event_log: SharedRealtimeEventLog,
+        backend: Option<Box<dyn RealtimeBackend>>,
+    ) -> SharedRealtimeHub {
+        let (pending, queue) = mpsc::unbounded_channel();
+
+        Arc::new_cyclic(|hub| {
+            tokio::spawn(record_events(hub.clone(), queue));
+
+            Self {
+                instance_id: uuid::Uuid::new_v4().to_string(),
+                state: RwLock::new(HubState::default()),
+                backend,
+                event_log,
+                pending,
+                typing_sent: Mutex::new(HashMap::new()),
+            }
+        })
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    // Brawlers with at least one open connection to this instance
+    pub fn connected_brawlers(&self) -> HashSet<i32> {
+        let state = self.state.read().unwrap();
+        state.inboxes.keys().copied().collect()
+    }
+
+    // False while this brawler's last typing event in the mission is younger than
+    // `window`, otherwise records now as the last one in the same lock
+    pub fn try_claim_typing(&self, mission_id: i32, brawler_id: i32, window: Duration) -> bool {
+        let now = Instant::now();
+        let mut typing_sent = self.typing_sent.lock().unwrap();
+        typing_sent.retain(|_, sent_at| now.duration_since(*sent_at) < window);
+        if typing_sent.contains_key(&(mission_id, brawler_id)) {
+            return false;
+        }
+
+        typing_sent.insert((mission_id, brawler_id), now);
+        true
+    }
+
+    pub fn broadcast(&self, event: RealtimeEvent) {
+        let audience = event.audience();
+        self.publish(audience, event);
+    }
+
+    pub fn publish(&self, audience: Audience, event: RealtimeEvent) {
+        if event.is_transient() {
+            self.dispatch(None, audience, event);
+        } else {
+            // Logged first so every instance sees the same id
+            let _ = self.pending.send((audience, event));
+        }
+    }
+
+    // Called by a backend for messages published on another instance
+    pub fn receive(&self, message: RealtimeMessage) {
+        if message.origin == self.instance_id {
+            return;
+        }
+
+        self.deliver(message.id, &message.audience, &message.event);
+    }
+
+    // Everything this brawler would have received after `after_id`, as far as
+    // the log still reaches back
+    pub async fn replay(&self, brawler_id: i32, after_id: i64) -> Result<Replay> {
+        let latest_id = self.event_log.latest_id().await?;
+        let oldest_id = self.event_log.oldest_id().await?;
+        if oldest_id.is_some_and(|oldest_id| after_id + 1 < oldest_id)
+            || latest_id.is_some_and(|latest_id| after_id > latest_id)
+        {
+            return Ok(Replay::ResyncRequired { latest_id });
+        }
+
+        let entities = self
+            .event_log
+            .get_after(after_id, MAX_REPLAY_EVENTS + 1)
+            .await?;
+        if entities.len() as i64 > MAX_REPLAY_EVENTS {
+            return Ok(Replay::ResyncRequired { latest_id });
+        }
+
+        let state = self.state.read().unwrap();
+        let rooms = state.memberships.get(&brawler_id);
+        let mut events = Vec::new();
+        for entity in entities {
+            let (audience, event) = match (entity.audience(), entity.event()) {
+                (Ok(audience), Ok(event)) => (audience, event),
+                _ => {
+                    tracing::warn!("Skipping unreadable realtime event {}", entity.id);
+                    continue;
+                }
+            };
+
+            let reaches = match audience {
+                Audience::User(id) => id == brawler_id,
+                Audience::Users(ids) => ids.contains(&brawler_id),
+                Audience::Mission(mission_id) => {
+                    rooms.is_some_and(|rooms| rooms.contains(&mission_id))
+                }
+                Audience::All => true,
+            };
+            if reaches {
+                events.push(RealtimeDelivery {
+                    id: Some(entity.id),
+                    event,
+                });
+            }
+        }
+
+        Ok(Replay::Events(events))
+    }
+
+    async fn record(&self, audience: Audience, event: RealtimeEvent) {
+        let id = match AddRealtimeEventEntity::new(&audience, &event) {
+            Ok(entity) => match self.event_log.append(entity).await {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    tracing::error!("Failed to log realtime event: {:?}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                tracing::error!("Failed to serialize realtime event: {:?}", e);
+                None
+            }
+        };
+
+        self.dispatch(id, audience, event);
+
+        if let Some(id) = id
+            && id % PRUNE_INTERVAL == 0
+            && let Err(e) = self.event_log.prune_before(id - RETAINED_EVENTS).await
+        {
+            tracing::error!("Failed to prune realtime events: {:?}", e);
+        }
+    }
+
+    fn dispatch(&self, id: Option<i64>, audience: Audience, event: RealtimeEvent) {
+        self.deliver(id, &audience, &event);
+
+        if let Some(backend) = &self.backend {
+            backend.publish(&RealtimeMessage {
+                origin: self.instance_id.clone(),
+                id,
+                audience,
+                event,
+            });
+        }
+    }
+
+    fn deliver(&self, id: Option<i64>, audience: &Audience, event: &RealtimeEvent) {
+        self.track_membership(event);
+
+        let state = self.state.read().unwrap();
+        let send = |brawler_id: &i32| {
+            if let Some(inbox) = state.inboxes.get(brawler_id) {
+                let _ = inbox.tx.send(RealtimeDelivery {
+                    id,
+                    event: event.clone(),
+                });
+            }
+        };
+
+        match audience {
+            Audience::User(brawler_id) => send(brawler_id),
+            Audience::Users(brawler_ids) => brawler_ids.iter().for_each(send),
+            Audience::Mission(mission_id) => {
+                if let Some(room) = state.rooms.get(mission_id) {
+                    room.iter().for_each(send);
+                }
+            }
+            Audience::All => state.inboxes.keys().for_each(send),
+        }
+    }
+
+    pub fn subscribe(self: &Arc<Self>, brawler_id: i32) -> RealtimeSubscription {
+        let mut state = self.state.write().unwrap();
+        let inbox = state.inboxes.entry(brawler_id).or_insert_with(|| Inbox {
+            tx: broadcast::channel(INBOX_CAPACITY).0,
+            connections: 0,
+        });
+        inbox.connections += 1;
+
+        RealtimeSubscription {
+            brawler_id,
+            rx: inbox.tx.subscribe(),
+            hub: Arc::clone(self),
+        }
+    }
+
+    // Only connected brawlers are tracked; the rest are loaded when they connect
+    pub fn join_room(&self, mission_id: i32, brawler_id: i32) {
+        let mut state = self.state.write().unwrap();
+        if !state.inboxes.contains_key(&brawler_id) {
+            return;
+        }
+
+        state
+            .rooms
+            .entry(mission_id)
+            .or_default()
+            .insert(brawler_id);
+        state
+            .memberships
+            .entry(brawler_id)
+            .or_default()
+            .insert(mission_id);
+    }
+
+    pub fn leave_room(&self, mission_id: i32, brawler_id: i32) {
+        let mut state = self.state.write().unwrap();
+        remove_from_room(&mut state, mission_id, brawler_id);
+    }
+
+    pub fn close_room(&self, mission_id: i32) {
+        let mut state = self.state.write().unwrap();
+        if let Some(room) = state.rooms.remove(&mission_id) {
+            for brawler_id in room {
+                if let Some(missions) = state.memberships.get_mut(&brawler_id) {
+                    missions.remove(&mission_id);
+                }
+            }
+        }
+    }
+
+    pub fn is_in_room(&self, mission_id: i32, brawler_id: i32) -> bool {
+        let state = self.state.read().unwrap();
+        state
+            .rooms
+            .get(&mission_id)
+            .is_some_and(|room| room.contains(&brawler_id))
+    }
+
+    // Rooms follow the membership events every use case already emits
+    fn track_membership(&self, event: &RealtimeEvent) {
+        match event {
+            RealtimeEvent::MissionCreated {
+                mission_id,
+                chief_id,
+            } => self.join_room(*mission_id, *chief_id),
+            RealtimeEvent::MissionJoined {
+                mission_id,
+                brawler_id,
+            } => self.join_room(*mission_id, *brawler_id),
+            RealtimeEvent::MissionInvitationAccepted {
+                mission_id,
+                user_id,
+                ..
+            } => self.join_room(*mission_id, *user_id),
+            RealtimeEvent::MissionLeft {
+                mission_id,
+                brawler_id,
+            } => self.leave_room(*mission_id, *brawler_id),
+            RealtimeEvent::MissionDeleted { mission_id } => self.close_room(*mission_id),
+            _ => {}
+        }
+    }
+
+    fn release(&self, brawler_id: i32) {
+        let mut state = self.state.write().unwrap();
+        let Some(inbox) = state.inboxes.get_mut(&brawler_id) else {
+            return;
+        };
+
+        inbox.connections -= 1;
+        if inbox.connections > 0 {
+            return;
+        }
+
+        state.inboxes.remove(&brawler_id);
+        if let Some(missions) = state.memberships.remove(&brawler_id) {
+            for mission_id in missions {
+                remove_from_room(&mut state, mission_id, brawler_id);
+            }
+        }
+    }
+}
+
+// Appends one event at a time so ids are handed out in publish order
+async fn record_events(
+    hub: Weak<RealtimeHub>,
+    mut queue: mpsc::UnboundedReceiver<(Audience, RealtimeEvent)>,
+) {
+    while let Some((audience, event)) = queue.recv().await {
+        let Some(hub) = hub.upgrade() else {
+            return;
+        };
+        hub.record(audience, event).await;
+    }
+}
+
+fn remove_from_room(state: &mut HubState, mission_id: i32, brawler_id: i32) {
+    if let Some(room) = state.rooms.get_mut(&mission_id) {
+        room.remove(&brawler_id);
+        if room.is_empty() {
+            state.rooms.remove(&mission_id);
+        }
+    }
+    if let Some(missions) = state.memberships.get_mut(&brawler_id) {
+        missions.remove(&mission_id);
+    }
+}
+
+// A single connection's view of a brawler inbox. Dropping it disconnects.
+pub struct RealtimeSubscription {
+    brawler_id: i32,
+    rx: broadcast::Receiver<RealtimeDelivery>,
+    hub: SharedRealtimeHub,
+}
+
+impl RealtimeSubscription {
+    pub fn brawler_id(&self) -> i32 {
+        self.brawler_id
+    }
+
+    pub async fn recv(&mut self) -> Result<RealtimeDelivery, RecvError> {
+        self.rx.recv().await
+    }
+
+    pub async fn replay(&self, after_id: i64) -> Result<Replay> {
+        self.hub.replay(self.brawler_id, after_id).await
+    }
+}
+
+impl Drop for RealtimeSubscription {
+    fn drop(&mut self) {
+        self.hub.release(self.brawler_id);
+    }
+}
+
+pub type SharedRealtimeHub = Arc<RealtimeHub>;
@@ -0,0 +1,133 @@
+use diesel::{Connection, PgConnection, RunQueryDsl, sql_query, sql_types::Text};
+use std::{
+    sync::{
+        Arc, Weak,
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
+    thread,
+    time::Duration,
+};
+use tracing::{error, info, warn};
+
+use super::{
+    RealtimeBackend, RealtimeHub, RealtimeMessage, SharedRealtimeEventLog, SharedRealtimeHub,
+};
+
+const CHANNEL: &str = "realtime_events";
+// NOTIFY rejects payloads of 8000 bytes or more
+const MAX_PAYLOAD_BYTES: usize = 7999;
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+// Publishes through Postgres NOTIFY so every instance LISTENing on the same
+// database re-broadcasts to its own clients
+pub struct PgNotifyBackend {
+    outgoing: Sender<String>,
+}
+
+impl RealtimeBackend for PgNotifyBackend {
+    fn publish(&self, message: &RealtimeMessage) {
+        let payload = match serde_json::to_string(message) {
+            Ok(payload) => payload,
+            Err(e) => {
+                error!("Failed to serialize realtime message: {}", e);
+                return;
+            }
+        };
+
+        if payload.len() > MAX_PAYLOAD_BYTES {
+            warn!(
+                "Realtime message of {} bytes is too large for NOTIFY, delivered locally only",
+                payload.len()
+            );
+            return;
+        }
+
+        let _ = self.outgoing.send(payload);
+    }
+}
+
+// Needs a direct connection: LISTEN does not survive a transaction pooler
+pub fn start_hub(database_url: String, event_log: SharedRealtimeEventLog) -> SharedRealtimeHub {
+    let (outgoing, queue) = mpsc::channel();
+    let hub = RealtimeHub::start(event_log, Some(Box::new(PgNotifyBackend { outgoing })));
+
+    // Weak so the thread winds down once the hub itself is dropped
+    let listener_hub = Arc::downgrade(&hub);
+    thread::Builder::new()
+        .name("realtime-pg-listener".to_string())
+        .spawn(move || run(&database_url, &queue, &listener_hub))
+        .expect("Failed to spawn realtime listener thread");
+
+    hub
+}
+
+// One dedicated connection both sends our NOTIFYs and polls for everyone else's
+fn run(database_url: &str, queue: &Receiver<String>, hub: &Weak<RealtimeHub>) {
+    loop {
+        let mut connection = match connect(database_url) {
+            Ok(connection) => connection,
+            Err(e) => {
+                error!("Realtime listener could not connect: {}", e);
+                thread::sleep(RECONNECT_DELAY);
+                continue;
+            }
+        };
+        info!("Realtime listener is listening on '{}'", CHANNEL);
+
+        match pump(&mut connection, queue, hub) {
+            Ok(()) => return,
+            Err(e) => {
+                // Events published while we reconnect are lost to other instances
+                error!("Realtime listener lost its connection: {}", e);
+                thread::sleep(RECONNECT_DELAY);
+            }
+        }
+    }
+}
+
+fn connect(database_url: &str) -> anyhow::Result<PgConnection> {
+    let mut connection = PgConnection::establish(database_url)?;
+    sql_query(format!("LISTEN {}", CHANNEL)).execute(&mut connection)?;
+
+    Ok(connection)
+}
+
+// Returns Ok only when the hub is gone and nothing can publish any more
+fn pump(
+    connection: &mut PgConnection,
+    queue: &Receiver<String>,
+    hub: &Weak<RealtimeHub>,
+) -> anyhow::Result<()> {
+    loop {
+        match queue.recv_timeout(POLL_INTERVAL) {
+            Ok(payload) => notify(connection, &payload)?,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        while let Ok(payload) = queue.try_recv() {
+            notify(connection, &payload)?;
+        }
+
+        for notification in connection.notifications_iter() {
+            let notification = notification?;
+            let Some(hub) = hub.upgrade() else {
+                return Ok(());
+            };
+
+            match serde_json::from_str::<RealtimeMessage>(&notification.payload) {
+                Ok(message) => hub.receive(message),
+                Err(e) => warn!("Ignoring malformed realtime notification: {}", e),
+            }
+        }
+    }
+}
+
+fn notify(connection: &mut PgConnection, payload: &str) -> anyhow::Result<()> {
+    sql_query("SELECT pg_notify($1, $2)")
+        .bind::<Text, _>(CHANNEL)
+        .bind::<Text, _>(payload)
+        .execute(connection)?;
+
+    Ok(())
+}
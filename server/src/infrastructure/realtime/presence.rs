@@ -0,0 +1,42 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use crate::{
+    application::use_cases::presence::PresenceUseCase,
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        repositories::{friendships::FriendshipPostgres, presence::PresencePostgres},
+    },
+};
+
+use super::SharedRealtimeHub;
+
+// Presence is sampled rather than pushed on every connect and disconnect, so a
+// page reload or a second tab never shows up as going offline and back
+const SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+pub fn spawn_tracker(db_pool: Arc<PgPoolSquad>, realtime_hub: SharedRealtimeHub) {
+    let use_case = PresenceUseCase::new(
+        Arc::new(PresencePostgres::new(Arc::clone(&db_pool))),
+        Arc::new(FriendshipPostgres::new(Arc::clone(&db_pool))),
+        Arc::clone(&realtime_hub),
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SYNC_INTERVAL);
+        let mut known: HashSet<i32> = HashSet::new();
+
+        loop {
+            interval.tick().await;
+
+            let current = realtime_hub.connected_brawlers();
+            let connected = current.difference(&known).copied().collect();
+            let disconnected = known.difference(&current).copied().collect();
+
+            // On failure the same difference is retried on the next tick
+            match use_case.sync(connected, disconnected).await {
+                Ok(()) => known = current,
+                Err(e) => tracing::error!("Failed to sync presence: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,41 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    application::use_cases::mission_scheduler::MissionSchedulerUseCase,
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                mission_management::MissionManagementPostgres,
+                mission_operation::MissionOperationPostgres,
+                mission_viewing::MissionViewingPostgres, notifications::NotificationPostgres,
+            },
+        },
+        realtime::SharedRealtimeHub,
+    },
+};
+
+// Deadlines, reminders and purges are honoured to within this
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+// Every instance runs one; the repository claims each change so it only
+// happens once
+pub fn spawn_mission_scheduler(db_pool: Arc<PgPoolSquad>, realtime_hub: SharedRealtimeHub) {
+    let use_case = MissionSchedulerUseCase::new(
+        Arc::new(MissionOperationPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(MissionViewingPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(NotificationPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(MissionManagementPostgres::new(Arc::clone(&db_pool))),
+        realtime_hub,
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            use_case.run().await;
+        }
+    });
+}